@@ -0,0 +1,192 @@
+//! Derive macros for [MoonScript](https://crates.io/crates/moon_script), these are re-exported by
+//! moon_script when its `derive` feature is enabled, so there is no need to depend on this crate
+//! directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, Visibility};
+
+/// Makes a struct usable as a MoonScript type, generating:
+///
+/// - `From<YourType> for MoonValue`, where structs with named fields become maps going from the
+///   name of each field to its value, tuple structs become arrays and unit structs become null.
+/// - `TryFrom<MoonValue> for YourType`, reading the values written by the previous conversion.
+/// - `YourType::register(&mut Engine)`, adding a `get_field` and a `set_field` property function
+///   for each public field, so scripts can use `value.field` and `value.field = new_value`, the
+///   setters write the modified value back into the variable it was read from.
+///
+/// Every field must implement both `Into<MoonValue>` and `TryFrom<MoonValue>`, with an error that
+/// can be turned into a `ConversionError`, and the struct can't have generics. Fields missing from
+/// a map are read as null, so a `Vec` field can be left out.
+///
+/// ```rust
+/// use moon_script::{ContextBuilder, Engine, InputVariable, MoonType};
+///
+/// #[derive(MoonType, Clone, Debug, PartialEq)]
+/// struct Player {
+///     pub name: String,
+///     pub hp: u16,
+///     level: u8,
+/// }
+///
+/// let mut engine = Engine::new();
+/// Player::register(&mut engine);
+/// let context = ContextBuilder::new()
+///     .with_variable(InputVariable::new("player").associated_type_of::<Player>());
+/// let ast = engine.parse("player.hp = player.hp - 5; player", context).unwrap();
+///
+/// let player = Player { name: "Moon".to_string(), hp: 20, level: 3 };
+/// let result: Player = ast.executor().push_variable("player", player.clone())
+///     .execute().unwrap().try_into().unwrap();
+/// assert_eq!(Player { hp: 15, ..player }, result);
+/// ```
+#[proc_macro_derive(MoonType)]
+pub fn derive_moon_type(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive_moon_type_impl(input).unwrap_or_else(Error::into_compile_error).into()
+}
+
+fn derive_moon_type_impl(input: DeriveInput) -> Result<TokenStream2, Error> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(&input.generics, "MoonType cannot be derived for types with generics"));
+    }
+    let fields = match input.data {
+        Data::Struct(data) => data.fields,
+        Data::Enum(data) => return Err(Error::new(data.enum_token.span, "MoonType can only be derived for structs")),
+        Data::Union(data) => return Err(Error::new(data.union_token.span, "MoonType can only be derived for structs")),
+    };
+    let type_name = input.ident;
+    let type_key = unraw(&type_name);
+    let (into_moon_value, from_moon_value) = match &fields {
+        Fields::Named(_) => named_conversions(&type_key, &fields),
+        Fields::Unnamed(_) => unnamed_conversions(&type_key, &fields),
+        Fields::Unit => (
+            quote! { ::moon_script::MoonValue::Null },
+            quote! {
+                match value {
+                    ::moon_script::MoonValue::Null => ::core::result::Result::Ok(Self),
+                    other => ::core::result::Result::Err(::moon_script::ConversionError::new(
+                        ::moon_script::MoonValueKind::from(#type_key), other)),
+                }
+            },
+        ),
+    };
+    let property_functions = property_functions(&type_name, &fields);
+
+    Ok(quote! {
+        impl ::core::convert::From<#type_name> for ::moon_script::MoonValue {
+            fn from(value: #type_name) -> Self {
+                #into_moon_value
+            }
+        }
+
+        impl ::core::convert::TryFrom<::moon_script::MoonValue> for #type_name {
+            type Error = ::moon_script::ConversionError;
+
+            fn try_from(value: ::moon_script::MoonValue) -> ::core::result::Result<Self, Self::Error> {
+                #from_moon_value
+            }
+        }
+
+        impl #type_name {
+            /// Adds a `get_field` and a `set_field` property function to the engine for each public
+            /// field of this type.
+            #[allow(unused_variables)]
+            pub fn register(engine: &mut ::moon_script::Engine) {
+                #(#property_functions)*
+            }
+        }
+    })
+}
+
+fn named_conversions(type_key: &str, fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let names = fields.iter().map(|field| field.ident.clone().unwrap()).collect::<Vec<_>>();
+    let keys = names.iter().map(unraw).collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+    let indexes = (0..names.len()).collect::<Vec<_>>();
+    let field_count = names.len();
+    let empty_slots = names.iter().map(|_| quote! { ::core::option::Option::None });
+
+    let into_moon_value = quote! {
+        ::moon_script::MoonValue::Map([
+            #((::moon_script::MoonValue::from(#keys), ::moon_script::MoonValue::from(value.#names)),)*
+        ].into_iter().collect())
+    };
+    let from_moon_value = quote! {
+        let entries = match value {
+            ::moon_script::MoonValue::Map(entries) => entries,
+            other => return ::core::result::Result::Err(::moon_script::ConversionError::new(
+                ::moon_script::MoonValueKind::from(#type_key), other)),
+        };
+        let mut field_values: [::core::option::Option<::moon_script::MoonValue>; #field_count] = [#(#empty_slots),*];
+        for (key, value) in entries {
+            if let ::moon_script::MoonValue::String(key) = key {
+                match key.as_str() {
+                    #(#keys => field_values[#indexes] = ::core::option::Option::Some(value),)*
+                    _ => {}
+                }
+            }
+        }
+        let [#(#names),*] = field_values;
+        ::core::result::Result::Ok(Self {
+            #(#names: <#types as ::core::convert::TryFrom<::moon_script::MoonValue>>::try_from(#names.unwrap_or(::moon_script::MoonValue::Null))
+                .map_err(|error| ::core::convert::Into::<::moon_script::ConversionError>::into(error).at_field(#keys))?,)*
+        })
+    };
+    (into_moon_value, from_moon_value)
+}
+
+fn unnamed_conversions(type_key: &str, fields: &Fields) -> (TokenStream2, TokenStream2) {
+    let field_indexes = (0..fields.len()).map(syn::Index::from).collect::<Vec<_>>();
+    let indexes = (0..fields.len()).collect::<Vec<_>>();
+    let field_count = fields.len();
+    let names = (0..fields.len()).map(|index| format_ident!("field_{index}")).collect::<Vec<_>>();
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
+    let into_moon_value = quote! {
+        ::moon_script::MoonValue::Array([#(::moon_script::MoonValue::from(value.#field_indexes)),*].into_iter().collect())
+    };
+    let from_moon_value = quote! {
+        let values = match value {
+            ::moon_script::MoonValue::Array(values) if values.len() == #field_count => values,
+            other => return ::core::result::Result::Err(::moon_script::ConversionError::new(
+                ::moon_script::MoonValueKind::from(#type_key), other)),
+        };
+        let mut values = values.into_iter();
+        #(let #names = <#types as ::core::convert::TryFrom<::moon_script::MoonValue>>::try_from(values.next().unwrap())
+            .map_err(|error| ::core::convert::Into::<::moon_script::ConversionError>::into(error).at_index(#indexes))?;)*
+        ::core::result::Result::Ok(Self(#(#names),*))
+    };
+    (into_moon_value, from_moon_value)
+}
+
+fn property_functions(type_name: &syn::Ident, fields: &Fields) -> Vec<TokenStream2> {
+    let Fields::Named(_) = fields else {
+        return Vec::new();
+    };
+    fields.iter()
+        .filter(|field| matches!(field.vis, Visibility::Public(_)))
+        .map(|field| {
+            let name = field.ident.as_ref().unwrap();
+            let field_type = &field.ty;
+            let getter_name = format!("get_{}", unraw(name));
+            let setter_name = format!("set_{}", unraw(name));
+            quote! {
+                engine.add_function(::moon_script::FunctionDefinition::new(#getter_name, |value: #type_name| value.#name)
+                    .associated_type_of::<#type_name>());
+                engine.add_function(::moon_script::FunctionDefinition::new_mutating(#setter_name, |mut value: #type_name, new_value: #field_type| {
+                    value.#name = new_value;
+                    ::moon_script::Mutation { argument: value, result: ::moon_script::MoonValue::Null }
+                })
+                    .associated_type_of::<#type_name>());
+            }
+        })
+        .collect()
+}
+
+/// Name of the field as written in scripts, without the `r#` prefix of raw identifiers.
+fn unraw(name: &syn::Ident) -> String {
+    let name = name.to_string();
+    name.strip_prefix("r#").map(str::to_string).unwrap_or(name)
+}
@@ -9,6 +9,7 @@ use pest::Parser;
 use crate::execution::ASTFunction;
 use crate::function::ToAbstractFunction;
 use crate::parsing::{Rule, SimpleParser};
+use crate::reduced_value_impl::CoercionPolicy;
 use crate::value::{FullValue, MoonValue};
 use crate::MoonValueKind;
 
@@ -21,6 +22,8 @@ pub struct ContextBuilder {
     pub(crate) started_parsing: bool,
     pub(crate) start_parsing_position_offset: (usize, usize),
     pub(crate) parsing_position_column_is_fixed: bool,
+    pub(crate) coercion_policy: Option<CoercionPolicy>,
+    pub(crate) used_modules: Vec<String>,
 }
 
 impl AsRef<ContextBuilder> for ContextBuilder {
@@ -38,6 +41,8 @@ impl Default for ContextBuilder {
             started_parsing: false,
             start_parsing_position_offset: (0, 0),
             parsing_position_column_is_fixed: false,
+            coercion_policy: None,
+            used_modules: vec![],
         };
         res.push_block_level();
         res
@@ -150,6 +155,37 @@ impl ContextBuilder {
     }
 
 
+    /// Uses this [CoercionPolicy] for this script instead of the one of the Engine parsing it.
+    pub fn coercion_policy(&mut self, coercion_policy: CoercionPolicy) {
+        self.coercion_policy = Some(coercion_policy)
+    }
+
+    /// Uses this [CoercionPolicy] for this script instead of the one of the Engine parsing it.
+    pub fn with_coercion_policy(mut self, coercion_policy: CoercionPolicy) -> ContextBuilder {
+        self.coercion_policy = Some(coercion_policy);
+        self
+    }
+
+    /// Lets the script call the functions of this module without naming the module, as if it
+    /// started with `use module_name;`, see [crate::Engine::with_prelude_module] for the order in
+    /// which modules are searched.
+    pub fn use_module<Name: ToString>(&mut self, module_name: Name) {
+        self.used_modules.push(module_name.to_string())
+    }
+
+    /// Lets the script call the functions of this module without naming the module, as if it
+    /// started with `use module_name;`, see [crate::Engine::with_prelude_module] for the order in
+    /// which modules are searched.
+    pub fn with_used_module<Name: ToString>(mut self, module_name: Name) -> ContextBuilder {
+        self.used_modules.push(module_name.to_string());
+        self
+    }
+
+    /// [CoercionPolicy] in use while parsing the script.
+    pub(crate) fn effective_coercion_policy(&self) -> CoercionPolicy {
+        self.coercion_policy.unwrap_or_default()
+    }
+
     pub(crate) fn push_variable_internal<Variable: Into<InputVariable>>(&mut self, variable: Variable, declare_variable_as_new: bool) -> (usize, usize) {
         let mut variable = variable.into();
         if !declare_variable_as_new {
@@ -212,14 +248,12 @@ impl InputVariable {
     /// but if the value isn't given in none of these ways and its used in the script, you should
     /// give this value to the AST's executor thorough [crate::ASTExecutor::push_variable] or
     /// [crate::OptimizedASTExecutor::push_variable].
+    ///
+    /// The name must be a valid identifier that isn't a keyword, otherwise parsing a script with
+    /// this variable fails with [crate::ASTBuildingError::InvalidVariableName].
     pub fn new<Name: ToString>(name: Name) -> Self {
-        let mut name = name.to_string();
-        let parsed = SimpleParser::parse(Rule::ident, &*name);
-        if parsed.is_err() || parsed.unwrap().as_str().len() < name.len() {
-            name = "Wrong variable name".to_string();
-        }
         Self {
-            name,
+            name: name.to_string(),
             first_value: FullValue::Null,
             associated_type_name: None,
             current_known_value: None,
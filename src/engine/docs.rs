@@ -0,0 +1,219 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::engine::Engine;
+use crate::parsing::RegisteredFunction;
+
+/// Format of the reference page written by [Engine::generate_docs].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocsFormat {
+    /// A Markdown document.
+    Markdown,
+    /// An HTML fragment with the same sections as the Markdown document, meant to be placed inside
+    /// the body of a page.
+    Html,
+}
+
+/// Writes the pieces of the reference page in the chosen format.
+struct DocsWriter {
+    format: DocsFormat,
+    page: String,
+}
+
+impl DocsWriter {
+    fn heading(&mut self, level: usize, text: &str) {
+        match self.format {
+            DocsFormat::Markdown => self.page.push_str(&format!("{} {text}\n\n", "#".repeat(level))),
+            DocsFormat::Html => self.page.push_str(&format!("<h{level}>{}</h{level}>\n", escape_html(text))),
+        }
+    }
+
+    fn code_heading(&mut self, level: usize, code: &str) {
+        match self.format {
+            DocsFormat::Markdown => self.page.push_str(&format!("{} `{code}`\n\n", "#".repeat(level))),
+            DocsFormat::Html => self.page.push_str(&format!("<h{level}><code>{}</code></h{level}>\n", escape_html(code))),
+        }
+    }
+
+    fn paragraph(&mut self, text: &str) {
+        match self.format {
+            DocsFormat::Markdown => self.page.push_str(&format!("{text}\n\n")),
+            DocsFormat::Html => self.page.push_str(&format!("<p>{}</p>\n", escape_html(text))),
+        }
+    }
+
+    /// Writes a list whose items are a piece of code followed by a description, which can be
+    /// empty.
+    fn code_list(&mut self, items: &[(String, String)]) {
+        match self.format {
+            DocsFormat::Markdown => {
+                items.iter().for_each(|(code, description)| {
+                    self.page.push_str(&format!("- `{code}`{}\n", prefix_space(description)))
+                });
+                self.page.push('\n');
+            }
+            DocsFormat::Html => {
+                self.page.push_str("<ul>\n");
+                items.iter().for_each(|(code, description)| {
+                    self.page.push_str(&format!("<li><code>{}</code>{}</li>\n", escape_html(code), escape_html(&prefix_space(description))))
+                });
+                self.page.push_str("</ul>\n");
+            }
+        }
+    }
+
+    fn function(&mut self, level: usize, function: &RegisteredFunction) {
+        self.code_heading(level, &signature(function));
+        if let Some(doc) = function.doc {
+            self.paragraph(doc);
+        }
+        if function.mutating {
+            self.paragraph("Writes its first argument back into the variable it was read from.");
+        }
+    }
+}
+
+fn prefix_space(text: &str) -> String {
+    if text.is_empty() { String::new() } else { format!(" {text}") }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+/// Writes how a function is called, like `string/len(value) -> int` or `player.heal(amount)`.
+fn signature(function: &RegisteredFunction) -> String {
+    let is_associated = function.associated_type_name.is_some();
+    let mut param_names = (0..function.number_of_params)
+        .map(|index| function.param_names
+            .and_then(|param_names| param_names.get(index).cloned())
+            .unwrap_or_else(|| match (is_associated, index) {
+                (true, 0) => "value".to_string(),
+                (true, index) => format!("arg{index}"),
+                (false, index) => format!("arg{}", index + 1),
+            }));
+    let call = match (function.associated_type_name, function.module_name) {
+        (Some(_), _) => {
+            let receiver = param_names.next().unwrap_or_else(|| "value".to_string());
+            format!("{receiver}.{}({})", function.name, param_names.collect::<Vec<_>>().join(", "))
+        }
+        (None, Some(module_name)) => format!("{module_name}/{}({})", function.name, param_names.collect::<Vec<_>>().join(", ")),
+        (None, None) => format!("{}({})", function.name, param_names.collect::<Vec<_>>().join(", ")),
+    };
+    match function.return_type_name {
+        Some(return_type_name) => format!("{call} -> {return_type_name}"),
+        None => call,
+    }
+}
+
+/// Property a type has thanks to a `get_name` or a `set_name` function, which scripts use as
+/// `value.name` and `value.name = new_value`.
+#[derive(Default)]
+struct Property<'engine> {
+    readable: bool,
+    writable: bool,
+    type_name: Option<&'engine str>,
+}
+
+/// Name of the property a function gives access to, if it's a getter or a setter.
+fn property_of<'engine>(function: &RegisteredFunction<'engine>) -> Option<(&'engine str, bool)> {
+    match (function.name.strip_prefix("get_"), function.name.strip_prefix("set_")) {
+        (Some(property), _) if function.number_of_params == 1 => Some((property, true)),
+        (_, Some(property)) if function.number_of_params == 2 => Some((property, false)),
+        _ => None,
+    }
+}
+
+pub(crate) fn generate_docs(engine: &Engine, format: DocsFormat) -> String {
+    let mut writer = DocsWriter { format, page: String::new() };
+    writer.heading(1, "Reference");
+    let functions = engine.functions();
+
+    let global_functions = functions.iter()
+        .filter(|function| function.associated_type_name.is_none() && function.module_name.is_none())
+        .collect::<Vec<_>>();
+    if !global_functions.is_empty() {
+        writer.heading(2, "Functions");
+        global_functions.into_iter().for_each(|function| writer.function(3, function));
+    }
+
+    let mut module_names = functions.iter()
+        .filter(|function| function.associated_type_name.is_none())
+        .filter_map(|function| function.module_name)
+        .collect::<Vec<_>>();
+    module_names.dedup();
+    for module_name in module_names {
+        writer.heading(2, &format!("Module {module_name}"));
+        functions.iter()
+            .filter(|function| function.associated_type_name.is_none() && function.module_name == Some(module_name))
+            .for_each(|function| writer.function(3, function));
+    }
+
+    let mut type_names = functions.iter()
+        .filter_map(|function| function.associated_type_name)
+        .collect::<Vec<_>>();
+    type_names.dedup();
+    for type_name in type_names {
+        writer.heading(2, &format!("Type {type_name}"));
+        let type_functions = functions.iter()
+            .filter(|function| function.associated_type_name == Some(type_name))
+            .collect::<Vec<_>>();
+
+        let mut properties = BTreeMap::<&str, Property>::new();
+        type_functions.iter()
+            .filter_map(|function| property_of(function).map(|(name, is_getter)| (name, is_getter, function)))
+            .for_each(|(name, is_getter, function)| {
+                let property = properties.entry(name).or_default();
+                if is_getter {
+                    property.readable = true;
+                    property.type_name = function.return_type_name;
+                } else {
+                    property.writable = true;
+                }
+            });
+        if !properties.is_empty() {
+            writer.heading(3, "Properties");
+            let items = properties.into_iter()
+                .map(|(name, property)| {
+                    let code = match property.type_name {
+                        Some(type_name) => format!("{name}: {type_name}"),
+                        None => name.to_string(),
+                    };
+                    let access = match (property.readable, property.writable) {
+                        (true, true) => "(read and write)",
+                        (true, false) => "(read only)",
+                        _ => "(write only)",
+                    };
+                    (code, access.to_string())
+                })
+                .collect::<Vec<_>>();
+            writer.code_list(&items);
+        }
+
+        let methods = type_functions.into_iter()
+            .filter(|function| property_of(function).is_none())
+            .collect::<Vec<_>>();
+        if !methods.is_empty() {
+            writer.heading(3, "Methods");
+            methods.into_iter().for_each(|function| writer.function(4, function));
+        }
+    }
+
+    let constants = engine.constants();
+    if !constants.is_empty() {
+        writer.heading(2, "Constants");
+        let items = constants.into_iter()
+            .map(|(name, constant)| {
+                let code = match constant.type_name() {
+                    Some(type_name) => format!("{name}: {type_name} = {}", constant.value()),
+                    None => format!("{name} = {}", constant.value()),
+                };
+                (code, String::new())
+            })
+            .collect::<Vec<_>>();
+        writer.code_list(&items);
+    }
+    writer.page
+}
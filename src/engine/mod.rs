@@ -1,42 +1,96 @@
 use alloc::string::{String, ToString};
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
 use log::trace;
 use pest::Parser;
 use simple_detailed_error::SimpleErrorDetail;
 
 use context::ContextBuilder;
+use docs::DocsFormat;
 
 use crate::execution::ast::AST;
 use crate::parsing::error::ParsingError;
-use crate::parsing::{FunctionDefinition, FunctionInfo, Rule, SimpleParser};
-use crate::reduced_value_impl::impl_operators;
+use crate::parsing::{FunctionDefinition, FunctionInfo, RegisteredFunction, Rule, SimpleParser};
+use crate::reduced_value_impl::impl_operators::OverflowPolicy;
+use crate::reduced_value_impl::{impl_built_in_associated_functions, CoercionPolicy, impl_operators, impl_std_array, impl_std_math, impl_std_random, impl_std_string};
+#[cfg(feature = "json")]
+use crate::reduced_value_impl::impl_std_json;
 use crate::value::MoonValue;
-use crate::{parsing, HashMap, MoonValueKind};
+use crate::{parsing, HashMap, HashSet, MoonValueKind};
 
 
 pub mod context;
+pub mod docs;
 
 #[derive(Clone)]
 /// Scripting engine, it allows to create runnable ASTs, and also to give functions and constant
 /// values for said scripts
 pub struct Engine {
-    //CustomType->RustModule->FunctionName->fn()
-    associated_functions: HashMap<String, HashMap<String, HashMap<String, FunctionInfo>>>,
-    //RustModule->FunctionName->fn()
-    functions: HashMap<String, HashMap<String, FunctionInfo>>,
+    //CustomType->RustModule->FunctionName->Overloads
+    associated_functions: HashMap<String, HashMap<String, HashMap<String, Vec<FunctionInfo>>>>,
+    //RustModule->FunctionName->Overloads
+    functions: HashMap<String, HashMap<String, Vec<FunctionInfo>>>,
 
-    //CustomType->FunctionName->fn()
-    built_in_associated_functions: HashMap<String, HashMap<String, FunctionInfo>>,
-    //FunctionName->fn()
-    built_in_functions: HashMap<String, FunctionInfo>,
+    //CustomType->FunctionName->Overloads
+    built_in_associated_functions: HashMap<String, HashMap<String, Vec<FunctionInfo>>>,
+    //FunctionName->Overloads
+    built_in_functions: HashMap<String, Vec<FunctionInfo>>,
 
     //OperatorName->Fn()
     binary_operators: HashMap<String, FunctionInfo>,
     //OperatorName->Fn()
     unary_operators: HashMap<String, FunctionInfo>,
+    //OperatorName->CustomType->Fn()
+    binary_operator_overloads: HashMap<String, HashMap<String, FunctionInfo>>,
+    //OperatorName->CustomType->Fn()
+    unary_operator_overloads: HashMap<String, HashMap<String, FunctionInfo>>,
+    //Operators replaced by the user, so changing the overflow policy keeps them
+    replaced_binary_operators: HashSet<String>,
+    replaced_unary_operators: HashSet<String>,
 
     constants: HashMap<String, Constant>,
+
+    coercion_policy: CoercionPolicy,
+    overflow_policy: OverflowPolicy,
+
+    //Modules searched, in order, for functions called without naming the module
+    prelude: Vec<String>,
+
+    //Engine whose functions, operators and constants are used when this one lacks them
+    parent: Option<Arc<Engine>>,
+
+    //Removed from this engine, so they are not looked for in the parent either
+    //(CustomType, RustModule, FunctionName)
+    removed_functions: HashSet<(Option<String>, Option<String>, String)>,
+    removed_modules: HashSet<String>,
+    removed_constants: HashSet<String>,
+}
+
+/// Reason why an operator couldn't be added, see [Engine::add_binary_operator] and
+/// [Engine::add_unary_operator].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OperatorError {
+    /// The language has no operator with this symbol taking as many operands.
+    UnknownOperator { operator: String },
+    /// The function doesn't receive as many arguments as the operator has operands.
+    WrongNumberOfParams { operator: String, expected: usize, found: usize },
+}
+
+impl Display for OperatorError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            OperatorError::UnknownOperator { operator } =>
+                write!(f, "There is no operator '{operator}' with that many operands"),
+            OperatorError::WrongNumberOfParams { operator, expected, found } =>
+                write!(f, "The operator '{operator}' has {expected} operands, but the function receives {found} arguments"),
+        }
+    }
 }
 
+#[cfg(feature = "std")]
+impl std::error::Error for OperatorError {}
+
 /// Defines a constant that will be inlined on scripts.
 #[derive(Clone)]
 pub struct Constant {
@@ -73,6 +127,11 @@ impl Constant{
     pub fn value(&self) -> &MoonValue {
         &self.value
     }
+
+    /// Gets the name of the type associated to this constant, if any.
+    pub fn type_name(&self) -> Option<&str> {
+        self.type_name.as_deref()
+    }
 }
 
 impl<T:Into<MoonValue>> From<T> for Constant{
@@ -87,25 +146,9 @@ impl<T:Into<MoonValue>> From<T> for Constant{
 
 impl Default for Engine {
     fn default() -> Self {
-        let res = Self {
-            associated_functions: Default::default(),
-            functions: Default::default(),
-            built_in_associated_functions: Default::default(),
-            built_in_functions: Default::default(),
-            binary_operators: impl_operators::get_binary_operators().into_iter()
-                .map(|(name, function)| {
-                    (name.to_string(), FunctionInfo::new(function).inline())
-                })
-                .collect(),
-            unary_operators: impl_operators::get_unary_operators().into_iter()
-                .map(|(name, function)| {
-                    (name.to_string(), FunctionInfo::new(function).inline())
-                })
-                .collect(),
-            constants: Default::default(),
-        };
-        #[cfg(feature = "std")]
-        let mut res = res;
+        let mut res = Self::empty();
+        impl_built_in_associated_functions::get_built_in_associated_functions().into_iter()
+            .for_each(|function| res.add_function(function));
         #[cfg(feature = "std")]
         res.add_function(FunctionDefinition::new("print", |value: String| {
             println!("{value}");
@@ -120,11 +163,311 @@ impl Default for Engine {
 
 
 impl Engine {
-    /// Creates a new empty Engine containing just basic functions, like println or binary operators
+    /// Creates a new empty Engine containing just basic functions, like println or binary operators,
+    /// and the associated functions of the built-in types, like `len` for strings and arrays or
+    /// `abs` for integers and decimals.
     pub fn new() -> Self {
         Default::default()
     }
 
+    /// Creates an Engine without any function or constant, not even `print`, `println` or the
+    /// associated functions of the built-in types, only the operators are kept as they are part of
+    /// the language, this is meant for sandboxed scripts that should only reach what the host
+    /// explicitly gives them.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine};
+    /// let engine = Engine::empty();
+    /// assert!(engine.functions().is_empty());
+    /// assert!(engine.parse("println(\"hi\");", ContextBuilder::new()).is_err());
+    /// let result: i32 = engine.parse("return 2 + 3;", ContextBuilder::new()).unwrap()
+    ///     .executor().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(5, result);
+    /// ```
+    pub fn empty() -> Self {
+        Self {
+            associated_functions: Default::default(),
+            functions: Default::default(),
+            built_in_associated_functions: Default::default(),
+            built_in_functions: Default::default(),
+            binary_operators: impl_operators::get_binary_operators(OverflowPolicy::default()).into_iter()
+                .map(|(name, function)| {
+                    (name.to_string(), FunctionInfo::new_raw(function).inline())
+                })
+                .chain(impl_operators::get_lazy_binary_operators().into_iter()
+                    .map(|(name, function)| {
+                        (name.to_string(), FunctionInfo::new_raw(function).inline())
+                    }))
+                .collect(),
+            unary_operators: impl_operators::get_unary_operators(OverflowPolicy::default()).into_iter()
+                .map(|(name, function)| {
+                    (name.to_string(), FunctionInfo::new_raw(function).inline())
+                })
+                .collect(),
+            binary_operator_overloads: Default::default(),
+            unary_operator_overloads: Default::default(),
+            replaced_binary_operators: Default::default(),
+            replaced_unary_operators: Default::default(),
+            constants: Default::default(),
+            coercion_policy: Default::default(),
+            overflow_policy: Default::default(),
+            prelude: Default::default(),
+            parent: None,
+            removed_functions: Default::default(),
+            removed_modules: Default::default(),
+            removed_constants: Default::default(),
+        }
+    }
+
+    /// Creates an Engine that inherits every function, operator and constant of the parent one,
+    /// along with its [CoercionPolicy] and [OverflowPolicy], without copying them, so many engines
+    /// can share the bindings they have in common.
+    ///
+    /// Whatever is added to the child overrides what the parent has under the same name, and
+    /// whatever is removed from the child is hidden from it, even if the parent has it, but only
+    /// for the child, so a sandbox can take functions like `print` away from a shared engine.
+    ///
+    /// ```rust
+    /// use std::sync::Arc;
+    /// use moon_script::{ContextBuilder, Engine, FunctionDefinition};
+    /// let mut shared = Engine::new();
+    /// shared.add_function(FunctionDefinition::new("greet", || "Hello").module_name("ui"));
+    /// shared.add_constant("MAX_HP", 100);
+    /// let shared = Arc::new(shared);
+    ///
+    /// let mut quests = Engine::child(&shared);
+    /// quests.add_constant("MAX_HP", 50);
+    /// let greeting: String = quests.parse("return ui/greet();", ContextBuilder::new()).unwrap()
+    ///     .executor().execute().unwrap().try_into().unwrap();
+    /// assert_eq!("Hello", greeting);
+    /// let max_hp: i32 = quests.parse("return MAX_HP;", ContextBuilder::new()).unwrap()
+    ///     .executor().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(50, max_hp);
+    /// let max_hp: i32 = shared.constant("MAX_HP").unwrap().value().clone().try_into().unwrap();
+    /// assert_eq!(100, max_hp);
+    ///
+    /// quests.remove_module("ui");
+    /// assert!(quests.parse("return ui/greet();", ContextBuilder::new()).is_err());
+    /// assert!(shared.parse("return ui/greet();", ContextBuilder::new()).is_ok());
+    /// ```
+    pub fn child(parent: &Arc<Engine>) -> Self {
+        Self {
+            associated_functions: Default::default(),
+            functions: Default::default(),
+            built_in_associated_functions: Default::default(),
+            built_in_functions: Default::default(),
+            binary_operators: Default::default(),
+            unary_operators: Default::default(),
+            binary_operator_overloads: Default::default(),
+            unary_operator_overloads: Default::default(),
+            replaced_binary_operators: Default::default(),
+            replaced_unary_operators: Default::default(),
+            constants: Default::default(),
+            coercion_policy: parent.coercion_policy,
+            overflow_policy: parent.overflow_policy,
+            prelude: Default::default(),
+            parent: Some(parent.clone()),
+            removed_functions: Default::default(),
+            removed_modules: Default::default(),
+            removed_constants: Default::default(),
+        }
+    }
+
+    /// Gets the engine this one inherits from, if it was created with [Self::child].
+    pub fn parent(&self) -> Option<&Arc<Engine>> {
+        self.parent.as_ref()
+    }
+
+    /// Adds the `string` module of the standard library, containing the functions len, upper,
+    /// lower, split, join, replace, starts_with, find, substring, repeat, pad_left and format, these
+    /// are inlined when their arguments are constant.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine};
+    /// let engine = Engine::new().with_std_string();
+    /// let result : String = engine.parse(r###"
+    ///     string/format("{} has {} hp", [string/upper("moon"), string/pad_left("7", 3, "0")])
+    /// "###, ContextBuilder::default()).unwrap().execute().unwrap().try_into().unwrap();
+    /// assert_eq!("MOON has 007 hp", result);
+    /// ```
+    pub fn with_std_string(mut self) -> Self {
+        impl_std_string::get_std_string_functions().into_iter()
+            .for_each(|function| self.add_function(function));
+        self
+    }
+
+    /// Adds the `math` module of the standard library, containing the functions abs, min, max,
+    /// clamp, pow, floor, ceil, round, sqrt, sin, cos, atan2, lerp and e, along with the constants
+    /// PI and TAU, these functions are inlined when their arguments are constant.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine};
+    /// let engine = Engine::new().with_std_math();
+    /// let result : f64 = engine.parse(r###"
+    ///     math/clamp(math/sqrt(16.0) * PI, 0.0, 10.0)
+    /// "###, ContextBuilder::default()).unwrap().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(10.0, result);
+    /// ```
+    pub fn with_std_math(mut self) -> Self {
+        impl_std_math::get_std_math_functions().into_iter()
+            .for_each(|function| self.add_function(function));
+        impl_std_math::get_std_math_constants().into_iter()
+            .for_each(|(name, constant)| { self.add_constant(name, constant); });
+        self
+    }
+
+    /// Adds the `array` module of the standard library, containing the functions len, push, pop,
+    /// insert, remove, contains, index_of, reverse, sort, concat, slice, sum and unique, these
+    /// functions are also added as methods of arrays, so both `array/push(values, 5)` and
+    /// `values.push(5)` can be used, the functions push, pop, insert, remove, reverse and sort
+    /// modify the variable holding the array.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine};
+    /// let engine = Engine::new().with_std_array();
+    /// let result : Vec<i32> = engine.parse(r###"
+    ///     let values = [3, 1, 2];
+    ///     values.push(0);
+    ///     array/sort(values);
+    ///     values
+    /// "###, ContextBuilder::default()).unwrap().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(vec![0, 1, 2, 3], result);
+    /// ```
+    pub fn with_std_array(mut self) -> Self {
+        impl_std_array::get_std_array_functions().into_iter()
+            .flat_map(|function| [
+                function.clone().module_name(impl_std_array::ARRAY_MODULE_NAME),
+                function.associated_type_name(MoonValueKind::Array),
+            ])
+            .for_each(|function| self.add_function(function));
+        self
+    }
+
+    /// Adds the `random` module of the standard library, containing the functions int, decimal,
+    /// choice and shuffle, these use a random number generator whose state belongs to each
+    /// executor, so giving the same seed through [crate::ASTExecutor::seed_random] or
+    /// [crate::OptimizedASTExecutor::seed_random] always gives the same values, executors that
+    /// aren't seeded use a fixed default seed.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine};
+    /// let engine = Engine::new().with_std_random();
+    /// let ast = engine.parse("[random/int(1, 6), random/choice([\"sword\", \"axe\"])]", ContextBuilder::default()).unwrap();
+    /// let roll = ast.executor().seed_random(42).execute().unwrap();
+    /// assert_eq!(roll, ast.executor().seed_random(42).execute().unwrap());
+    /// ```
+    pub fn with_std_random(mut self) -> Self {
+        impl_std_random::get_std_random_functions().into_iter()
+            .for_each(|function| self.add_function(function));
+        self
+    }
+
+    /// Adds the `json` module of the standard library, containing the functions encode and decode,
+    /// which work as [MoonValue::to_json] and [MoonValue::from_json], these are inlined when their
+    /// arguments are constant.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine};
+    /// let engine = Engine::new().with_std_json();
+    /// let result : String = engine.parse(r###"
+    ///     let position = json/decode("[1, 2.5]");
+    ///     json/encode([position, 2.0, "moon"])
+    /// "###, ContextBuilder::default()).unwrap().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(r#"[[1,2.5],2.0,"moon"]"#, result);
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn with_std_json(mut self) -> Self {
+        impl_std_json::get_std_json_functions().into_iter()
+            .for_each(|function| self.add_function(function));
+        self
+    }
+
+    /// Sets which conversions between kinds of values are done silently on the scripts parsed by
+    /// this engine, [CoercionPolicy::Lenient] by default, see [CoercionPolicy] for what each policy
+    /// allows, a [ContextBuilder] can override it for a single script.
+    ///
+    /// ```rust
+    /// use moon_script::{CoercionPolicy, ContextBuilder, Engine};
+    /// let engine = Engine::new().with_coercion_policy(CoercionPolicy::NumericOnly);
+    /// assert!(engine.parse("1 + 2.5", ContextBuilder::default()).is_ok());
+    /// assert!(engine.parse("true + 1", ContextBuilder::default()).is_err());
+    /// ```
+    pub fn with_coercion_policy(mut self, coercion_policy: CoercionPolicy) -> Self {
+        self.coercion_policy = coercion_policy;
+        self
+    }
+
+    /// Gets the [CoercionPolicy] used by the scripts parsed by this engine, unless their
+    /// [ContextBuilder] overrides it.
+    pub fn coercion_policy(&self) -> CoercionPolicy {
+        self.coercion_policy
+    }
+
+    /// Adds a module to the prelude of the engine, letting scripts call its functions without
+    /// naming the module, as if every script started with `use module_name;`.
+    ///
+    /// Functions called without a module are searched, in this order, between the functions that
+    /// don't belong to any module, the modules used by the script with `use module_name;` at its
+    /// start, the ones given with [ContextBuilder::use_module] and the prelude modules in the order
+    /// they were added, the first module having the function is the one used. Otherwise the
+    /// function is searched between every module, failing to compile if many modules have it.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine, FunctionDefinition};
+    /// let mut engine = Engine::new();
+    /// engine.add_function(FunctionDefinition::new("roll", || 4).module_name("dice"));
+    /// engine.add_function(FunctionDefinition::new("roll", || 6).module_name("loaded_dice"));
+    /// assert!(engine.parse("return roll();", ContextBuilder::new()).is_err());
+    ///
+    /// let engine = engine.with_prelude_module("loaded_dice");
+    /// let result: i32 = engine.parse("return roll();", ContextBuilder::new()).unwrap()
+    ///     .executor().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(6, result);
+    ///
+    /// let result: i32 = engine.parse("use dice; return roll();", ContextBuilder::new()).unwrap()
+    ///     .executor().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(4, result);
+    /// ```
+    pub fn with_prelude_module<Name: ToString>(mut self, module_name: Name) -> Self {
+        self.prelude.push(module_name.to_string());
+        self
+    }
+
+    /// Sets what happens when an operation between integers overflows, which by default fails
+    /// with a [crate::RuntimeError::Arithmetic], see [OverflowPolicy], this replaces the built-in
+    /// operators of the engine. The [CoercionPolicy] is applied to the operators when parsing each
+    /// script, so both policies can be given in any order.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine, OverflowPolicy};
+    /// let script = "170141183460469231731687303715884105727 + 1";
+    /// assert!(Engine::new().parse(script, ContextBuilder::default()).is_err());
+    ///
+    /// let engine = Engine::new().with_overflow_policy(OverflowPolicy::Wrapping);
+    /// let result : i128 = engine.parse(script, ContextBuilder::default()).unwrap()
+    ///     .executor().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(i128::MIN, result);
+    /// ```
+    pub fn with_overflow_policy(mut self, overflow_policy: OverflowPolicy) -> Self {
+        self.overflow_policy = overflow_policy;
+        let binary_operators = impl_operators::get_binary_operators(overflow_policy).into_iter()
+            .filter(|(name, _)| !self.replaces_operator(name, false))
+            .map(|(name, function)| (name.to_string(), FunctionInfo::new_raw(function).inline()))
+            .collect::<Vec<_>>();
+        let unary_operators = impl_operators::get_unary_operators(overflow_policy).into_iter()
+            .filter(|(name, _)| !self.replaces_operator(name, true))
+            .map(|(name, function)| (name.to_string(), FunctionInfo::new_raw(function).inline()))
+            .collect::<Vec<_>>();
+        self.binary_operators.extend(binary_operators);
+        self.unary_operators.extend(unary_operators);
+        self
+    }
+
+    /// Gets the [OverflowPolicy] of the built-in operators of this engine.
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
     /// Adds a constant with a value
     ///
     /// ```rust
@@ -138,7 +481,9 @@ impl Engine {
     /// assert_eq!(15, result);
     /// ```
     pub fn add_constant<Name: ToString, Value: Into<Constant>>(&mut self, name: Name, value: Value) -> Option<Constant> {
-        self.constants.insert(name.to_string(), value.into())
+        let name = name.to_string();
+        self.removed_constants.remove(&name);
+        self.constants.insert(name, value.into())
     }
 
     /// Adds a function with a name
@@ -156,27 +501,248 @@ impl Engine {
     /// let result : i32 = runnable_ast.executor().execute().unwrap().try_into().unwrap();
     /// assert_eq!(5, result);
     /// ```
+    ///
+    /// Many functions can share a name as long as they receive a different number of arguments, or
+    /// arguments of different types, being overloads of each other, adding a function whose name
+    /// and parameters are already taken replaces it. Calls choose the overload by the number of
+    /// arguments and, when the types of the arguments are known, by their types, if that's not
+    /// enough the first overload receiving the arguments is called when running the script, and if
+    /// many overloads receive arguments of known types the script fails to compile.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine, FunctionDefinition};
+    /// let mut engine = Engine::new();
+    /// engine.add_function(FunctionDefinition::new("area", |side: f64| side * side));
+    /// engine.add_function(FunctionDefinition::new("area", |width: f64, height: f64| width * height));
+    /// engine.add_function(FunctionDefinition::new("describe", |value: i64| format!("number {value}")));
+    /// engine.add_function(FunctionDefinition::new("describe", |value: String| format!("text {value}")));
+    /// let result: f64 = engine.parse("return area(2.0) + area(2.0, 3.0);", ContextBuilder::new()).unwrap()
+    ///     .executor().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(10.0, result);
+    /// let result: String = engine.parse("return describe(\"five\");", ContextBuilder::new()).unwrap()
+    ///     .executor().execute().unwrap().try_into().unwrap();
+    /// assert_eq!("text five", result);
+    /// ```
     pub fn add_function<Function: Into<FunctionDefinition>>(&mut self, function_definition: Function) {
         let function_definition = function_definition.into();
         trace!("Adding function: {function_definition:?}");
-        match (function_definition.associated_type_name, function_definition.module_name) {
-            (None, None) => {
-                self.built_in_functions.insert(function_definition.function_name, function_definition.function_info);
+        self.removed_functions.remove(&(function_definition.associated_type_name.clone(), function_definition.module_name.clone(), function_definition.function_name.clone()));
+        let overloads = match (function_definition.associated_type_name, function_definition.module_name) {
+            (None, None) => self.built_in_functions.entry(function_definition.function_name).or_default(),
+            (Some(associated_type), None) => self.built_in_associated_functions.entry(associated_type).or_default()
+                .entry(function_definition.function_name).or_default(),
+            (None, Some(module_name)) => self.functions.entry(module_name).or_default()
+                .entry(function_definition.function_name).or_default(),
+            (Some(associated_type), Some(module_name)) => self.associated_functions.entry(associated_type).or_default()
+                .entry(module_name).or_default()
+                .entry(function_definition.function_name).or_default(),
+        };
+        let function_info = function_definition.function_info;
+        match overloads.iter_mut().find(|overload| overload.has_same_parameters(&function_info)) {
+            Some(overload) => *overload = function_info,
+            None => overloads.push(function_info),
+        }
+    }
+
+    /// Adds a binary operator, the name of the function is the symbol of the operator, like `+`,
+    /// and it has to be one of the binary operators of the language, see [Self::binary_operators].
+    ///
+    /// If the function is associated to a type, it overloads the operator for said type, being
+    /// used when any of the operands is of that type, otherwise it replaces the operator for every
+    /// type that doesn't overload it. When the types of the operands are known while parsing the
+    /// overload is chosen then, otherwise the overloads for built-in types, like `int` or `array`,
+    /// are tried when running the script, sorted by type name, using the first one whose type is
+    /// the kind of an operand and the operator itself if none is. Values don't carry custom types
+    /// when running, so those overloads are only used when the type is known while parsing.
+    ///
+    /// Replacing an operator keeps it when changing the [OverflowPolicy] afterwards.
+    ///
+    /// Fails with [OperatorError::UnknownOperator] if the symbol isn't a binary operator of the
+    /// language, and with [OperatorError::WrongNumberOfParams] if the function doesn't receive two
+    /// arguments, leaving the engine as it was.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine, FunctionDefinition, InputVariable};
+    /// let mut engine = Engine::new();
+    /// engine.add_binary_operator(FunctionDefinition::new("+", |lhs: Vec<f64>, rhs: Vec<f64>| {
+    ///     [lhs[0] + rhs[0], lhs[1] + rhs[1]]
+    /// }).associated_type_name("vec2").inline()).unwrap();
+    /// let context = ContextBuilder::new()
+    ///     .with_variable(InputVariable::new("position").associated_type("vec2").value(vec![1.0, 2.0]))
+    ///     .with_variable(InputVariable::new("speed").associated_type("vec2").value(vec![0.5, 0.5]));
+    /// let result: Vec<f64> = engine.parse("return position + speed;", context).unwrap()
+    ///     .executor().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(vec![1.5, 2.5], result);
+    /// ```
+    pub fn add_binary_operator<Function: Into<FunctionDefinition>>(&mut self, function_definition: Function) -> Result<(), OperatorError> {
+        let function_definition = function_definition.into();
+        let operator = function_definition.function_name;
+        if self.find_binary_operator(&operator).is_none() {
+            return Err(OperatorError::UnknownOperator { operator });
+        }
+        check_operands(&operator, 2, &function_definition.function_info)?;
+        trace!("Adding binary operator: {operator}");
+        match function_definition.associated_type_name {
+            None => {
+                self.replaced_binary_operators.insert(operator.clone());
+                self.binary_operators.insert(operator, function_definition.function_info);
+            }
+            Some(associated_type) => {
+                self.binary_operator_overloads.entry(operator).or_default()
+                    .insert(associated_type, function_definition.function_info);
+            }
+        }
+        Ok(())
+    }
+
+    /// Adds an unary operator, the name of the function is the symbol of the operator, like `-`,
+    /// and it has to be one of the unary operators of the language, see [Self::unary_operators],
+    /// overloads work like in [Self::add_binary_operator].
+    ///
+    /// Fails with [OperatorError::UnknownOperator] if the symbol isn't an unary operator of the
+    /// language, and with [OperatorError::WrongNumberOfParams] if the function doesn't receive one
+    /// argument.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine, FunctionDefinition, InputVariable};
+    /// let mut engine = Engine::new();
+    /// engine.add_unary_operator(FunctionDefinition::new("-", |vector: Vec<f64>| [-vector[0], -vector[1]])
+    ///     .associated_type_name("vec2")).unwrap();
+    /// let context = ContextBuilder::new()
+    ///     .with_variable(InputVariable::new("speed").associated_type("vec2").value(vec![0.5, 2.0]));
+    /// let result: Vec<f64> = engine.parse("return -speed;", context).unwrap()
+    ///     .executor().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(vec![-0.5, -2.0], result);
+    /// ```
+    pub fn add_unary_operator<Function: Into<FunctionDefinition>>(&mut self, function_definition: Function) -> Result<(), OperatorError> {
+        let function_definition = function_definition.into();
+        let operator = function_definition.function_name;
+        if self.find_unary_operator(&operator).is_none() {
+            return Err(OperatorError::UnknownOperator { operator });
+        }
+        check_operands(&operator, 1, &function_definition.function_info)?;
+        trace!("Adding unary operator: {operator}");
+        match function_definition.associated_type_name {
+            None => {
+                self.replaced_unary_operators.insert(operator.clone());
+                self.unary_operators.insert(operator, function_definition.function_info);
+            }
+            Some(associated_type) => {
+                self.unary_operator_overloads.entry(operator).or_default()
+                    .insert(associated_type, function_definition.function_info);
+            }
+        }
+        Ok(())
+    }
+
+    /// Removes a function along with its overloads, returning their definitions so they can be
+    /// added again later, adding a function whose name and parameters are already taken replaces
+    /// it instead, so this is only needed for taking functions away, like `print` and `println`
+    /// from sandboxed scripts, on engines made with [Self::child] the functions of the parent are
+    /// hidden too.
+    ///
+    /// Functions are identified by the type they are associated to, their module and their name,
+    /// the same way they were given to [FunctionDefinition].
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine, FunctionDefinition};
+    /// let mut engine = Engine::new();
+    /// engine.add_function(FunctionDefinition::new("write_file", |_path: String| {}).module_name("io"));
+    /// let write_file = engine.remove_function(None, Some("io"), "write_file");
+    /// assert!(engine.remove_function(None, Some("io"), "write_file").is_empty());
+    /// assert!(engine.parse("io/write_file(\"save.txt\");", ContextBuilder::new()).is_err());
+    /// write_file.into_iter().for_each(|function| engine.add_function(function));
+    /// assert!(engine.parse("io/write_file(\"save.txt\");", ContextBuilder::new()).is_ok());
+    /// ```
+    pub fn remove_function(&mut self, associated_type_name: Option<&str>, module_name: Option<&str>, function_name: &str) -> Vec<FunctionDefinition> {
+        let mut removed = self.remove_overloads(associated_type_name, module_name, function_name).unwrap_or_default();
+        if let Some(parent) = self.parent.clone() {
+            if !self.hides_function(associated_type_name, module_name, function_name) {
+                let inherited_overloads = parent.find_function_in(associated_type_name, module_name, function_name).into_iter()
+                    .filter(|inherited| !removed.iter().any(|overload| overload.has_same_parameters(inherited)))
+                    .cloned()
+                    .collect::<Vec<_>>();
+                removed.extend(inherited_overloads);
             }
+            self.removed_functions.insert((associated_type_name.map(ToString::to_string), module_name.map(ToString::to_string), function_name.to_string()));
+        }
+        removed.into_iter()
+            .map(|function_info| FunctionDefinition {
+                associated_type_name: associated_type_name.map(ToString::to_string),
+                module_name: module_name.map(ToString::to_string),
+                function_name: function_name.to_string(),
+                function_info,
+            })
+            .collect()
+    }
+
+    fn remove_overloads(&mut self, associated_type_name: Option<&str>, module_name: Option<&str>, function_name: &str) -> Option<Vec<FunctionInfo>> {
+        match (associated_type_name, module_name) {
+            (None, None) => self.built_in_functions.remove(function_name),
             (Some(associated_type), None) => {
-                self.built_in_associated_functions.entry(associated_type).or_default()
-                    .insert(function_definition.function_name, function_definition.function_info);
+                let functions = self.built_in_associated_functions.get_mut(associated_type)?;
+                let function_info = functions.remove(function_name);
+                if functions.is_empty() { self.built_in_associated_functions.remove(associated_type); }
+                function_info
             }
             (None, Some(module_name)) => {
-                self.functions.entry(module_name).or_default()
-                    .insert(function_definition.function_name, function_definition.function_info);
+                let functions = self.functions.get_mut(module_name)?;
+                let function_info = functions.remove(function_name);
+                if functions.is_empty() { self.functions.remove(module_name); }
+                function_info
             }
             (Some(associated_type), Some(module_name)) => {
-                self.associated_functions.entry(associated_type).or_default()
-                    .entry(module_name).or_default()
-                    .insert(function_definition.function_name, function_definition.function_info);
+                let modules = self.associated_functions.get_mut(associated_type)?;
+                let functions = modules.get_mut(module_name)?;
+                let function_info = functions.remove(function_name);
+                if functions.is_empty() { modules.remove(module_name); }
+                if modules.is_empty() { self.associated_functions.remove(associated_type); }
+                function_info
+            }
+        }
+    }
+
+    /// Removes every function of a module, including the functions of said module that are
+    /// associated to a type, returning whether there was any.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine};
+    /// let mut engine = Engine::new().with_std_math();
+    /// assert!(engine.remove_module("math"));
+    /// assert!(!engine.remove_module("math"));
+    /// assert!(engine.parse("return math/sqrt(4.0);", ContextBuilder::new()).is_err());
+    /// ```
+    pub fn remove_module(&mut self, module_name: &str) -> bool {
+        let mut removed = self.parent.is_some() && self.functions().iter().any(|function| function.module_name == Some(module_name));
+        if self.parent.is_some() {
+            self.removed_modules.insert(module_name.to_string());
+        }
+        removed |= self.functions.remove(module_name).is_some();
+        self.associated_functions.retain(|_, modules| {
+            removed |= modules.remove(module_name).is_some();
+            !modules.is_empty()
+        });
+        removed
+    }
+
+    /// Removes a constant, returning it if it existed.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine, MoonValue};
+    /// let mut engine = Engine::new();
+    /// engine.add_constant("MAX_HP", 100);
+    /// assert_eq!(Some(MoonValue::from(100)), engine.remove_constant("MAX_HP").map(|constant| constant.value().clone()));
+    /// assert!(engine.parse("return MAX_HP;", ContextBuilder::new()).is_err());
+    /// ```
+    pub fn remove_constant(&mut self, name: &str) -> Option<Constant> {
+        let mut removed = self.constants.remove(name);
+        if let Some(parent) = self.parent.clone() {
+            if removed.is_none() && !self.removed_constants.contains(name) {
+                removed = parent.constant(name).cloned();
             }
+            self.removed_constants.insert(name.to_string());
         }
+        removed
     }
 
     /// Parses a script into an AST using a specific context
@@ -205,6 +771,8 @@ impl Engine {
         let successful_parse = SimpleParser::parse(Rule::BASE_STATEMENTS, input)
             .map_err(|e| ParsingError::Grammar(e))?
             .next().unwrap();
+        let mut context_builder = context_builder;
+        context_builder.coercion_policy.get_or_insert(self.coercion_policy);
         parsing::build_ast(successful_parse.clone(), self, context_builder)
             .map_err(|errors| {
                 let mut error = "Could not compile.".to_string().to_simple_error();
@@ -215,47 +783,296 @@ impl Engine {
 
     pub(crate) fn find_unary_operator(&self, operator_name: &str) -> Option<&FunctionInfo> {
         self.unary_operators.get(operator_name)
+            .or_else(|| self.parent.as_ref()?.find_unary_operator(operator_name))
     }
 
     pub(crate) fn find_binary_operator(&self, operator_name: &str) -> Option<&FunctionInfo> {
         self.binary_operators.get(operator_name)
+            .or_else(|| self.parent.as_ref()?.find_binary_operator(operator_name))
     }
 
-    pub(crate) fn find_function(&self, type_name: Option<String>, module_name: Option<&str>, function_name: &str) -> Option<&FunctionInfo> {
-        if let Some(type_name) = type_name {
-            if let Some(module_name) = module_name.clone() {
-                self.associated_functions.get(&type_name)
-                    .map(|assoc_map| assoc_map.get(module_name)
-                        .map(|module_map| module_map.get(function_name)))
-                    .flatten().flatten()
-            } else {
-                let resolve_from_built_in_associated = self.built_in_associated_functions.get(&type_name)
-                    .map(|assoc_map| assoc_map.get(function_name)).flatten();
-                if resolve_from_built_in_associated.is_some() { return resolve_from_built_in_associated; }
-                let resolve_from_users_associated_functions = self.associated_functions.get(&type_name)
-                    .map(|assoc_map| assoc_map.iter()
-                        .map(|(_, module_map)| module_map.get(function_name))
-                        .next()
-                    ).flatten().flatten();
-                resolve_from_users_associated_functions
-            }
-        } else {
-            if let Some(module_name) = module_name.clone() {
-                self.functions.get(module_name)
-                    .map(|module_map| module_map.get(function_name))
-                    .flatten()
-            } else {
-                let resolve_from_built_in_functions = self.built_in_functions.get(function_name);
-                if resolve_from_built_in_functions.is_some() { return resolve_from_built_in_functions; }
-                let resolve_from_user_functions = self.functions.iter()
-                    .map(|(_, module_map)| module_map.get(function_name))
-                    .next().flatten();
-                resolve_from_user_functions
-            }
+    /// Overloads of a binary operator along with the types they are for, sorted by type name.
+    pub(crate) fn binary_operator_overloads(&self, operator_name: &str) -> Vec<(&str, &FunctionInfo)> {
+        self.operator_overloads(operator_name, false)
+    }
+
+    /// Overloads of an unary operator along with the types they are for, sorted by type name.
+    pub(crate) fn unary_operator_overloads(&self, operator_name: &str) -> Vec<(&str, &FunctionInfo)> {
+        self.operator_overloads(operator_name, true)
+    }
+
+    /// Tells if the operator was replaced with [Self::add_binary_operator] or
+    /// [Self::add_unary_operator], here or in the parent.
+    fn replaces_operator(&self, operator_name: &str, is_unary: bool) -> bool {
+        let replaced_operators = if is_unary { &self.replaced_unary_operators } else { &self.replaced_binary_operators };
+        replaced_operators.contains(operator_name)
+            || self.parent.as_ref().is_some_and(|parent| parent.replaces_operator(operator_name, is_unary))
+    }
+
+    fn operator_overloads(&self, operator_name: &str, is_unary: bool) -> Vec<(&str, &FunctionInfo)> {
+        let own_overloads = if is_unary { &self.unary_operator_overloads } else { &self.binary_operator_overloads };
+        let mut overloads = own_overloads.get(operator_name).into_iter()
+            .flat_map(|overloads| overloads.iter())
+            .map(|(type_name, function)| (type_name.as_str(), function))
+            .collect::<Vec<_>>();
+        if let Some(parent) = &self.parent {
+            let inherited_overloads = parent.operator_overloads(operator_name, is_unary).into_iter()
+                .filter(|(inherited_type_name, _)| !overloads.iter().any(|(type_name, _)| type_name == inherited_type_name))
+                .collect::<Vec<_>>();
+            overloads.extend(inherited_overloads);
+        }
+        overloads.sort_by_key(|(type_name, _)| *type_name);
+        overloads
+    }
+
+    /// Finds the function a script calls, when no module is given the function is searched, in
+    /// order, between the functions that don't belong to any module, the modules the script uses
+    /// followed by the prelude of the engine, see [Self::with_prelude_module], and lastly between
+    /// every module, failing with the names of the modules that have it if there are many.
+    ///
+    /// Gives every overload of the function, none if it doesn't exist.
+    pub(crate) fn find_function(&self, type_name: Option<&str>, module_name: Option<&str>, function_name: &str, used_modules: &[String]) -> Result<Vec<&FunctionInfo>, Vec<&str>> {
+        if module_name.is_some() {
+            return Ok(self.find_function_in(type_name, module_name, function_name));
+        }
+        let from_used_modules = [None].into_iter()
+            .chain(used_modules.iter().map(|module_name| Some(module_name.as_str())))
+            .chain(self.prelude_modules().into_iter().map(Some))
+            .map(|module_name| self.find_function_in(type_name, module_name, function_name))
+            .find(|overloads| !overloads.is_empty());
+        if let Some(overloads) = from_used_modules {
+            return Ok(overloads);
+        }
+        match self.modules_with_function(type_name, function_name).as_slice() {
+            [] => Ok(Vec::new()),
+            [module_name] => Ok(self.find_function_in(type_name, Some(module_name), function_name)),
+            module_names => Err(module_names.to_vec()),
+        }
+    }
+
+    /// Overloads of a function, along with the ones of the parent engine that this one doesn't
+    /// replace.
+    fn find_function_in(&self, type_name: Option<&str>, module_name: Option<&str>, function_name: &str) -> Vec<&FunctionInfo> {
+        let own_overloads = match (type_name, module_name) {
+            (None, None) => self.built_in_functions.get(function_name),
+            (Some(type_name), None) => self.built_in_associated_functions.get(type_name)
+                .and_then(|functions| functions.get(function_name)),
+            (None, Some(module_name)) => self.functions.get(module_name)
+                .and_then(|functions| functions.get(function_name)),
+            (Some(type_name), Some(module_name)) => self.associated_functions.get(type_name)
+                .and_then(|modules| modules.get(module_name))
+                .and_then(|functions| functions.get(function_name)),
+        };
+        let mut overloads = own_overloads.into_iter().flatten().collect::<Vec<_>>();
+        if let Some(parent) = self.parent.as_ref().filter(|_| !self.hides_function(type_name, module_name, function_name)) {
+            let inherited_overloads = parent.find_function_in(type_name, module_name, function_name).into_iter()
+                .filter(|inherited| !overloads.iter().any(|overload| overload.has_same_parameters(inherited)))
+                .collect::<Vec<_>>();
+            overloads.extend(inherited_overloads);
+        }
+        overloads
+    }
+
+    /// Tells if this function was removed from this engine, so it must not be looked for in the
+    /// parent engine.
+    fn hides_function(&self, associated_type_name: Option<&str>, module_name: Option<&str>, function_name: &str) -> bool {
+        module_name.is_some_and(|module_name| self.removed_modules.contains(module_name))
+            || (!self.removed_functions.is_empty() && self.removed_functions.contains(&(
+                associated_type_name.map(ToString::to_string), module_name.map(ToString::to_string), function_name.to_string())))
+    }
+
+    /// Names of the modules having a function with this name, sorted.
+    fn modules_with_function(&self, type_name: Option<&str>, function_name: &str) -> Vec<&str> {
+        let mut module_names = match type_name {
+            None => self.functions.iter()
+                .filter(|(_, functions)| functions.contains_key(function_name))
+                .map(|(module_name, _)| module_name.as_str())
+                .collect::<Vec<_>>(),
+            Some(type_name) => self.associated_functions.get(type_name).into_iter()
+                .flat_map(|modules| modules.iter())
+                .filter(|(_, functions)| functions.contains_key(function_name))
+                .map(|(module_name, _)| module_name.as_str())
+                .collect::<Vec<_>>(),
+        };
+        if let Some(parent) = &self.parent {
+            module_names.extend(parent.modules_with_function(type_name, function_name).into_iter()
+                .filter(|module_name| !self.hides_function(type_name, Some(module_name), function_name)));
+        }
+        module_names.sort();
+        module_names.dedup();
+        module_names
+    }
+
+    pub(crate) fn has_module(&self, module_name: &str) -> bool {
+        self.functions.contains_key(module_name)
+            || self.associated_functions.values().any(|modules| modules.contains_key(module_name))
+            || self.parent.as_ref().is_some_and(|parent| !self.removed_modules.contains(module_name) && parent.has_module(module_name))
+    }
+
+    /// Modules whose functions can be called without naming the module, in the order they are
+    /// searched, the ones of this engine go before the ones of its parent.
+    pub fn prelude_modules(&self) -> Vec<&str> {
+        let mut prelude_modules = self.prelude.iter().map(String::as_str).collect::<Vec<_>>();
+        if let Some(parent) = &self.parent {
+            prelude_modules.extend(parent.prelude_modules());
         }
+        prelude_modules
     }
-    pub(crate) fn constants(&self) -> &HashMap<String, Constant> {
-        &self.constants
+
+    /// Gets the constant with this name, if any, looking into the parent engine if this one
+    /// doesn't have it.
+    pub fn constant(&self, name: &str) -> Option<&Constant> {
+        self.constants.get(name)
+            .or_else(|| self.parent.as_ref().filter(|_| !self.removed_constants.contains(name))?.constant(name))
     }
 
+    /// Lists the constants of this engine along with their names, sorted by name.
+    ///
+    /// ```rust
+    /// use moon_script::{Engine, MoonValue};
+    /// let mut engine = Engine::new();
+    /// engine.add_constant("MAX_HP", 100);
+    /// let constants = engine.constants();
+    /// assert_eq!(("MAX_HP", &MoonValue::from(100)), (constants[0].0, constants[0].1.value()));
+    /// ```
+    pub fn constants(&self) -> Vec<(&str, &Constant)> {
+        let mut constants = self.constants.iter()
+            .map(|(name, constant)| (name.as_str(), constant))
+            .collect::<Vec<_>>();
+        if let Some(parent) = &self.parent {
+            constants.extend(parent.constants().into_iter()
+                .filter(|(name, _)| !self.constants.contains_key(*name) && !self.removed_constants.contains(*name)));
+        }
+        constants.sort_by_key(|(name, _)| *name);
+        constants
+    }
+
+    /// Lists every function scripts can call, including the associated functions of the built-in
+    /// types, sorted by associated type, module and name, operators aren't included, see
+    /// [Self::binary_operators] and [Self::unary_operators] for them.
+    ///
+    /// ```rust
+    /// use moon_script::{Engine, FunctionDefinition};
+    /// let mut engine = Engine::new();
+    /// engine.add_function(FunctionDefinition::new("heal", |hp: u16, amount: u16| hp + amount)
+    ///     .module_name("combat").inline());
+    /// let heal = engine.functions().into_iter()
+    ///     .find(|function| function.module_name == Some("combat")).unwrap();
+    /// assert_eq!(("heal", 2, true, Some("int")), (heal.name, heal.number_of_params, heal.inline, heal.return_type_name));
+    /// ```
+    pub fn functions(&self) -> Vec<RegisteredFunction<'_>> {
+        let mut functions = self.function_overloads().into_iter()
+            .map(|(type_name, module_name, name, function)| function.describe(name, module_name, type_name))
+            .collect::<Vec<_>>();
+        functions.sort_by_key(|function| (function.associated_type_name, function.module_name, function.name, function.number_of_params));
+        functions
+    }
+
+    /// Every overload of every function, along with the type it's associated to, its module and
+    /// its name, the ones of the parent engine are included if this one doesn't replace them.
+    fn function_overloads(&self) -> Vec<(Option<&str>, Option<&str>, &str, &FunctionInfo)> {
+        let built_in_functions = self.built_in_functions.iter()
+            .flat_map(|(name, overloads)| overloads.iter()
+                .map(move |function| (None, None, name.as_str(), function)));
+        let module_functions = self.functions.iter()
+            .flat_map(|(module_name, functions)| functions.iter()
+                .flat_map(move |(name, overloads)| overloads.iter()
+                    .map(move |function| (None, Some(module_name.as_str()), name.as_str(), function))));
+        let built_in_associated_functions = self.built_in_associated_functions.iter()
+            .flat_map(|(type_name, functions)| functions.iter()
+                .flat_map(move |(name, overloads)| overloads.iter()
+                    .map(move |function| (Some(type_name.as_str()), None, name.as_str(), function))));
+        let associated_functions = self.associated_functions.iter()
+            .flat_map(|(type_name, modules)| modules.iter()
+                .flat_map(move |(module_name, functions)| functions.iter()
+                    .flat_map(move |(name, overloads)| overloads.iter()
+                        .map(move |function| (Some(type_name.as_str()), Some(module_name.as_str()), name.as_str(), function)))));
+        let mut functions = built_in_functions
+            .chain(module_functions)
+            .chain(built_in_associated_functions)
+            .chain(associated_functions)
+            .collect::<Vec<_>>();
+        if let Some(parent) = &self.parent {
+            let inherited_functions = parent.function_overloads().into_iter()
+                .filter(|(inherited_type_name, inherited_module_name, inherited_name, _)| !self.hides_function(*inherited_type_name, *inherited_module_name, inherited_name))
+                .filter(|(inherited_type_name, inherited_module_name, inherited_name, inherited)| !functions.iter()
+                    .any(|(type_name, module_name, name, function)| (type_name, module_name, name) == (inherited_type_name, inherited_module_name, inherited_name)
+                        && function.has_same_parameters(inherited)))
+                .collect::<Vec<_>>();
+            functions.extend(inherited_functions);
+        }
+        functions
+    }
+
+    /// Writes a reference page listing every function, property and constant of this engine,
+    /// grouped by module and by associated type, properties are the `get_name` and `set_name`
+    /// functions of a type, which scripts use as `value.name`, see [FunctionDefinition::doc] and
+    /// [FunctionDefinition::param_names] for describing functions.
+    ///
+    /// ```rust
+    /// use moon_script::{DocsFormat, Engine, FunctionDefinition};
+    /// let mut engine = Engine::new();
+    /// engine.add_function(FunctionDefinition::new("get_hp", |hp: u16| hp).associated_type_name("Player"));
+    /// engine.add_constant("MAX_HP", 100);
+    /// let docs = engine.generate_docs(DocsFormat::Html);
+    /// assert!(docs.contains("<h2>Type Player</h2>\n<h3>Properties</h3>\n<ul>\n<li><code>hp: int</code> (read only)</li>"));
+    /// assert!(docs.contains("<li><code>MAX_HP: int = 100</code></li>"));
+    /// ```
+    pub fn generate_docs(&self, format: DocsFormat) -> String {
+        docs::generate_docs(self, format)
+    }
+
+    /// Lists the binary operators scripts can use, like `+` or `in`, sorted.
+    pub fn binary_operators(&self) -> Vec<&str> {
+        let mut operators = self.binary_operators.keys().map(String::as_str).collect::<Vec<_>>();
+        if let Some(parent) = &self.parent {
+            operators.extend(parent.binary_operators());
+        }
+        operators.sort();
+        operators.dedup();
+        operators
+    }
+
+    /// Lists the unary operators scripts can use, like `!`, sorted.
+    pub fn unary_operators(&self) -> Vec<&str> {
+        let mut operators = self.unary_operators.keys().map(String::as_str).collect::<Vec<_>>();
+        if let Some(parent) = &self.parent {
+            operators.extend(parent.unary_operators());
+        }
+        operators.sort();
+        operators.dedup();
+        operators
+    }
+
+    /// Lists the names of the types this engine knows of, these are the types functions are
+    /// associated to, along with the types of the constants and of the values functions return,
+    /// sorted and without duplicates.
+    ///
+    /// ```rust
+    /// use moon_script::{Constant, Engine};
+    /// let mut engine = Engine::new();
+    /// engine.add_constant("ORIGIN", Constant::new(0).associated_type("Position"));
+    /// assert!(engine.type_names().contains(&"Position"));
+    /// assert!(engine.type_names().contains(&"string"));
+    /// ```
+    pub fn type_names(&self) -> Vec<&str> {
+        let mut type_names = self.functions().into_iter()
+            .flat_map(|function| [function.associated_type_name, function.return_type_name])
+            .chain(self.constants().into_iter().map(|(_, constant)| constant.type_name()))
+            .flatten()
+            .collect::<Vec<_>>();
+        type_names.sort();
+        type_names.dedup();
+        type_names
+    }
+
+}
+
+/// Fails if a function given for an operator doesn't receive as many arguments as it has operands.
+fn check_operands(operator: &str, expected: usize, function: &FunctionInfo) -> Result<(), OperatorError> {
+    let found = function.number_of_params();
+    if found != expected {
+        return Err(OperatorError::WrongNumberOfParams { operator: operator.to_string(), expected, found });
+    }
+    Ok(())
 }
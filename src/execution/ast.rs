@@ -1,11 +1,14 @@
 use alloc::fmt::Debug;
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use core::any::Any;
 use core::mem;
 
 use crate::execution::{ASTFunction, ConditionalStatements, RuntimeError, RuntimeVariable};
 use crate::execution::optimized_ast::OptimizedAST;
+use crate::function::ExecutionState;
 use crate::HashMap;
+use crate::reduced_value_impl::impl_std_random;
 use crate::value::{FullValue, MoonValue};
 
 /// Compiled Script
@@ -54,15 +57,15 @@ struct ExecutingContext {
 }
 
 impl ExecutingContext {
-    fn execute_block(&mut self, block: &Statement) -> Result<Option<MoonValue>, RuntimeError> {
+    fn execute_block(&mut self, block: &Statement, state: &ExecutionState) -> Result<Option<MoonValue>, RuntimeError> {
         log::trace!("Executing block:\n{block:#?}");
         log::trace!("Variables at this point are:\n{:#?}", self.variables);
         match block {
             Statement::WhileBlock { condition, statements } => {
-                while self.resolve_value(condition.clone())?.try_into()
+                while self.resolve_value(condition.clone(), state)?.try_into()
                     .map_err(|_| RuntimeError::CannotTurnPredicateToBool { type_of_statement: "while", function_error_message: "".to_string() })? {
                     for statement in statements.iter() {
-                        if let Some(res) = self.execute_block(statement)? {
+                        if let Some(res) = self.execute_block(statement, state)? {
                             return Ok(Some(res));
                         }
                     }
@@ -70,12 +73,12 @@ impl ExecutingContext {
             }
             Statement::IfElseBlock { conditional_statements: conditional_blocks } => {
                 for block in conditional_blocks {
-                    let boolean : bool = self.resolve_value(block.condition.clone())?.try_into()
+                    let boolean : bool = self.resolve_value(block.condition.clone(), state)?.try_into()
                         .map_err(|_| RuntimeError::CannotTurnPredicateToBool { type_of_statement: "if", function_error_message: "".to_string() })?;
                     if boolean {
                         log::trace!("Executing statements of if block:\n{:#?}", block.statements);
                         for statement in block.statements.iter() {
-                            if let Some(res) = self.execute_block(statement)? {
+                            if let Some(res) = self.execute_block(statement, state)? {
                                 return Ok(Some(res));
                             }
                         }
@@ -85,19 +88,28 @@ impl ExecutingContext {
             }
             Statement::UnoptimizedAssignament { .. } => { unreachable!() }
             Statement::OptimizedAssignament { var_index, value } => {
-                self.variables[*var_index] = RuntimeVariable::new(self.resolve_value(value.clone())?)
+                self.variables[*var_index] = RuntimeVariable::new(self.resolve_value(value.clone(), state)?)
             }
             Statement::FnCall(function) => {
-                function.function.execute_iter(function.args.iter().map(|arg| self.resolve_value(arg.clone())))?;
+                self.execute_function(function, state)?;
             }
             Statement::ReturnCall(value) => {
-                return Ok(Some(self.resolve_value(value.clone())?));
+                return Ok(Some(self.resolve_value(value.clone(), state)?));
             }
         }
         Ok(None)
     }
 
-    fn resolve_value(&mut self, value: FullValue) -> Result<MoonValue, RuntimeError> {
+    fn execute_function(&mut self, function: &ASTFunction, state: &ExecutionState) -> Result<MoonValue, RuntimeError> {
+        let value = function.function.execute_iter_with_state(function.args.iter().map(|arg| self.resolve_value(arg.clone(), state)), state)?;
+        let (mutated_argument, result) = function.function.split_mutation(value);
+        if let (Some(mutated_argument), Some(FullValue::DirectVariable(variable_index))) = (mutated_argument, function.args.first()) {
+            self.variables[*variable_index] = RuntimeVariable::new(FullValue::from(mutated_argument));
+        }
+        Ok(result)
+    }
+
+    fn resolve_value(&mut self, value: FullValue, state: &ExecutionState) -> Result<MoonValue, RuntimeError> {
         Ok(match value {
             FullValue::Null => MoonValue::Null,
             FullValue::Boolean(bool) => MoonValue::Boolean(bool),
@@ -106,7 +118,7 @@ impl ExecutingContext {
             FullValue::String(string) => MoonValue::String(string),
             FullValue::Array(value) => {
                 let mut res = Vec::with_capacity(value.len());
-                for value in value.into_iter().map(|value| self.resolve_value(value)) {
+                for value in value.into_iter().map(|value| self.resolve_value(value, state)) {
                     match value {
                         Ok(value) => res.push(value),
                         Err(error) => return Err(error),
@@ -114,13 +126,18 @@ impl ExecutingContext {
                 }
                 MoonValue::Array(res)
             }
-            FullValue::Function(function) =>
-                function.function.execute_iter(function.args.iter()
-                    .map(|arg| self.resolve_value(arg.clone())))?,
+            FullValue::Map(entries) => {
+                let mut res = Vec::with_capacity(entries.len());
+                for (key, value) in entries.into_iter() {
+                    res.push((self.resolve_value(key, state)?, self.resolve_value(value, state)?));
+                }
+                MoonValue::Map(res)
+            }
+            FullValue::Function(function) => self.execute_function(&function, state)?,
             FullValue::Variable { .. } => unreachable!(),
             FullValue::DirectVariable(variable_index) => {
                 let variable = mem::replace(&mut self.variables[variable_index].value, FullValue::Null);
-                let res = self.resolve_value(variable)?;
+                let res = self.resolve_value(variable, state)?;
                 self.variables[variable_index] = RuntimeVariable::new(FullValue::from(res.clone()));
                 res
             }
@@ -133,12 +150,13 @@ impl ExecutingContext {
 pub struct ASTExecutor<'ast> {
     ast: &'ast AST,
     context: ExecutingContext,
+    random_state: u64,
 }
 
 impl<'ast> ASTExecutor<'ast> {
 
     pub(crate) fn new(ast: &'ast AST) -> Self {
-        Self { ast, context: ExecutingContext { variables: ast.variables.clone() } }
+        Self { ast, context: ExecutingContext { variables: ast.variables.clone() }, random_state: impl_std_random::DEFAULT_SEED }
     }
 
     /// Pushes a variable to this executor, if it is possible, it's preferred for you to push
@@ -151,10 +169,42 @@ impl<'ast> ASTExecutor<'ast> {
         self
     }
 
+    /// Seeds the random number generator used by the functions of the `random` module of this
+    /// executor, see [crate::Engine::with_std_random].
+    pub fn seed_random(mut self, seed: u64) -> Self {
+        self.random_state = seed;
+        self
+    }
+
     /// Executes the AST.
-    pub fn execute(mut self) -> Result<MoonValue, RuntimeError> {
+    pub fn execute(self) -> Result<MoonValue, RuntimeError> {
+        self.execute_with_host(None)
+    }
+
+    /// Executes the AST giving the state of the host to the functions it calls, these are
+    /// functions whose first parameter is `&mut State` or `&State`, which scripts call without
+    /// said parameter, failing with [RuntimeError::StateNotGiven] if no state of that type was
+    /// given.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine, FunctionDefinition};
+    /// struct World { gold: u32 }
+    /// let mut engine = Engine::new();
+    /// engine.add_function(FunctionDefinition::new("earn", |world: &mut World, amount: u32| world.gold += amount));
+    /// engine.add_function(FunctionDefinition::new("gold", |world: &World| world.gold));
+    /// let ast = engine.parse("earn(5); earn(gold()); return gold();", ContextBuilder::new()).unwrap();
+    /// let mut world = World { gold: 10 };
+    /// let result: u32 = ast.executor().execute_with_state(&mut world).unwrap().try_into().unwrap();
+    /// assert_eq!((30, 30), (result, world.gold));
+    /// ```
+    pub fn execute_with_state<State: Any>(self, state: &mut State) -> Result<MoonValue, RuntimeError> {
+        self.execute_with_host(Some(state))
+    }
+
+    fn execute_with_host(mut self, host: Option<&mut dyn Any>) -> Result<MoonValue, RuntimeError> {
+        let state = ExecutionState::new(self.random_state, host);
         for block in self.ast.statements.iter() {
-            if let Some(res) = self.context.execute_block(&block)? {
+            if let Some(res) = self.context.execute_block(&block, &state)? {
                 return Ok(res);
             }
         }
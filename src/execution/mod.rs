@@ -1,4 +1,5 @@
 use alloc::fmt::Debug;
+use alloc::boxed::Box;
 use alloc::format;
 use alloc::string::String;
 use alloc::string::ToString;
@@ -9,7 +10,9 @@ use simple_detailed_error::{SimpleErrorDetail, SimpleErrorExplanation};
 use ast::Statement;
 
 use crate::function::MoonFunction;
-use crate::value::FullValue;
+use crate::reduced_value_impl::ConversionError;
+use crate::reduced_value_impl::impl_operators::ArithmeticError;
+use crate::value::{FullValue, MoonValue};
 
 pub mod optimized_ast;
 pub mod ast;
@@ -23,10 +26,28 @@ pub enum RuntimeError {
     /// A predicate couldn't be calculated, this is the same as a [RuntimeError::FunctionError], but
     /// specific for 'if' and 'while' predicates.
     CannotTurnPredicateToBool { type_of_statement: &'static str, function_error_message: String },
-    /// An argument to a function couldn't be parsed as a [crate::MoonValue].
-    CannotParseArgument,
+    /// An argument given to a function couldn't be turned into the Rust type the function receives,
+    /// 'argument_position' starts at 1, counting the value a property is called on.
+    CannotParseArgument { function_name: String, argument_position: usize, expected_type: &'static str, error: Box<ConversionError> },
     /// A function tried to run, but an argument was missing.
     AnArgumentIsMissing,
+    /// A destructuring assignment like 'let [x, y] = value;' received a value whose shape doesn't
+    /// match the pattern.
+    CannotDestructure { pattern: String, value: MoonValue },
+    /// A value spread like '...values' wasn't an array.
+    CannotSpread { value: MoonValue },
+    /// A function received more arguments than it has parameters, as it can happen when spreading
+    /// an array into its arguments.
+    TooManyArguments { expected: usize, found: usize },
+    /// The predicate of an 'assert' statement resolved to false, 'line' and 'column' point to
+    /// where the predicate starts in the script.
+    AssertionFailed { predicate: String, message: Option<String>, line: usize, column: usize },
+    /// An arithmetic operation between integers couldn't be calculated, 'operation' is the
+    /// operation as written in scripts, like '5 / 0'.
+    Arithmetic { operation: String, error: ArithmeticError },
+    /// A function receiving the state of the host was called, but the executor wasn't given a
+    /// state of the type it expects, see [ast::ASTExecutor::execute_with_state].
+    StateNotGiven { function_name: String, expected_type: &'static str },
 }
 
 impl RuntimeError {
@@ -36,8 +57,23 @@ impl RuntimeError {
                 format!("Could not parse predicate of a {type_of_statement} block due to: {function_error_message}"),
             RuntimeError::FunctionError { function_error_message } =>
                 format!("Could not execute a function due to: {function_error_message}"),
-            RuntimeError::CannotParseArgument => "A function argument type is wrong".to_string(),
+            RuntimeError::CannotParseArgument { function_name, argument_position, expected_type, error } =>
+                format!("Argument {argument_position} of function '{function_name}' could not be turned into a {expected_type}, {error}"),
             RuntimeError::AnArgumentIsMissing => "A function is missing an argument".to_string(),
+            RuntimeError::CannotDestructure { pattern, value } =>
+                format!("The value {value} does not match the destructuring pattern {pattern}"),
+            RuntimeError::CannotSpread { value } =>
+                format!("Only arrays can be spread, but the value is: {value}"),
+            RuntimeError::TooManyArguments { expected, found } =>
+                format!("A function expects {expected} arguments, but it received {found}"),
+            RuntimeError::AssertionFailed { predicate, message: Some(message), line, column } =>
+                format!("Assertion '{predicate}' at line {line}, column {column} failed: {message}"),
+            RuntimeError::AssertionFailed { predicate, message: None, line, column } =>
+                format!("Assertion '{predicate}' at line {line}, column {column} failed"),
+            RuntimeError::Arithmetic { operation, error } =>
+                format!("Could not calculate {operation} because {error}"),
+            RuntimeError::StateNotGiven { function_name, expected_type } =>
+                format!("Function '{function_name}' needs a {expected_type} given to the executor with 'execute_with_state'"),
         }
     }
 }
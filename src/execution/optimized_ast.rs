@@ -3,14 +3,16 @@ use alloc::fmt::Debug;
 use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
+use core::any::Any;
 use core::mem;
 use core::ops::Range;
 
 use crate::execution::ast::AST;
 use crate::execution::ast::Statement;
 use crate::execution::RuntimeError;
-use crate::function::MoonFunction;
+use crate::function::{ExecutionState, MoonFunction};
 use crate::HashMap;
+use crate::reduced_value_impl::impl_std_random;
 use crate::value::{FullValue, MoonValue};
 
 const OPTIMIZED_AST_CONTENT_TYPE_BLOCK: u8 = 0;
@@ -80,6 +82,8 @@ enum OptimizedFullValue {
     Decimal(f64),
     String(String),
     Array(MultiDirection<OPTIMIZED_AST_CONTENT_TYPE_VALUE>),
+    /// Keys and values are stored interleaved, starting by the first key.
+    Map(MultiDirection<OPTIMIZED_AST_CONTENT_TYPE_VALUE>),
     Function(OptimizedASTFunction),
     DirectVariable(usize),
 }
@@ -166,6 +170,9 @@ impl OptimizedAST {
                 FullValue::Decimal(v) => OptimizedFullValue::Decimal(v),
                 FullValue::String(v) => OptimizedFullValue::String(v),
                 FullValue::Array(v) => OptimizedFullValue::Array(self.optimize_values(v)),
+                FullValue::Map(v) => OptimizedFullValue::Map(self.optimize_values(v.into_iter()
+                    .flat_map(|(key, value)| [key, value])
+                    .collect())),
                 FullValue::Function(v) =>
                     OptimizedFullValue::Function(OptimizedASTFunction {
                         function: v.function,
@@ -205,11 +212,12 @@ struct OptimizedExecutingContext {
 pub struct OptimizedASTExecutor<'ast> {
     ast: &'ast OptimizedAST,
     context: OptimizedExecutingContext,
+    random_state: u64,
 }
 
 impl<'ast> OptimizedASTExecutor<'ast> {
     pub(crate) fn new(ast: &'ast OptimizedAST) -> Self {
-        Self { ast, context: OptimizedExecutingContext { variables: ast.variables.clone() } }
+        Self { ast, context: OptimizedExecutingContext { variables: ast.variables.clone() }, random_state: impl_std_random::DEFAULT_SEED }
     }
 
     /// Pushes a variable to this executor, if it is possible, it's preferred for you to push
@@ -221,10 +229,28 @@ impl<'ast> OptimizedASTExecutor<'ast> {
         self
     }
 
+    /// Seeds the random number generator used by the functions of the `random` module of this
+    /// executor, see [crate::Engine::with_std_random].
+    pub fn seed_random(mut self, seed: u64) -> Self {
+        self.random_state = seed;
+        self
+    }
+
     /// Executes the AST.
-    pub fn execute(mut self) -> Result<MoonValue, RuntimeError> {
+    pub fn execute(self) -> Result<MoonValue, RuntimeError> {
+        self.execute_with_host(None)
+    }
+
+    /// Executes the AST giving the state of the host to the functions it calls, see
+    /// [crate::ASTExecutor::execute_with_state].
+    pub fn execute_with_state<State: Any>(self, state: &mut State) -> Result<MoonValue, RuntimeError> {
+        self.execute_with_host(Some(state))
+    }
+
+    fn execute_with_host(mut self, host: Option<&mut dyn Any>) -> Result<MoonValue, RuntimeError> {
+        let state = ExecutionState::new(self.random_state, host);
         for block in self.ast.statements.iter() {
-            if let Some(res) = self.context.execute_block(&self.ast.blocks[block], &self.ast)? {
+            if let Some(res) = self.context.execute_block(&self.ast.blocks[block], &self.ast, &state)? {
                 return Ok(res);
             }
         }
@@ -232,12 +258,13 @@ impl<'ast> OptimizedASTExecutor<'ast> {
     }
 
     pub fn execute_stack(mut self) -> Result<MoonValue, RuntimeError> {
+        let state = ExecutionState::new(self.random_state, None);
         let mut stacked_execution_blocks = VecDeque::with_capacity(25);
         self.ast.statements.iter().rev().for_each(|dir| stacked_execution_blocks.push_front(dir));
         while let Some(block_dir) = stacked_execution_blocks.pop_front() {
             match &self.ast.blocks[block_dir] {
                 OptimizedBlock::WhileBlock { condition, statements } => {
-                    if self.context.resolve_value(condition.dir, &self.ast)?.try_into()
+                    if self.context.resolve_value(condition.dir, &self.ast, &state)?.try_into()
                         .map_err(|_| RuntimeError::CannotTurnPredicateToBool { type_of_statement: "while", function_error_message: "".to_string() })? {
                         stacked_execution_blocks.push_front(block_dir);
                         statements.iter().rev().for_each(|dir| stacked_execution_blocks.push_front(dir));
@@ -247,7 +274,7 @@ impl<'ast> OptimizedASTExecutor<'ast> {
                     for if_block_dir in blocks.iter() {
                         match &self.ast.blocks[if_block_dir] {
                             OptimizedBlock::IfBlock { condition, statements } => {
-                                if self.context.resolve_value(condition.dir, &self.ast)?.try_into()
+                                if self.context.resolve_value(condition.dir, &self.ast, &state)?.try_into()
                                     .map_err(|_| RuntimeError::CannotTurnPredicateToBool { type_of_statement: "if", function_error_message: "".to_string() })? {
                                     statements.iter().rev().for_each(|dir| stacked_execution_blocks.push_front(dir));
                                     break;
@@ -259,13 +286,13 @@ impl<'ast> OptimizedASTExecutor<'ast> {
                 }
                 OptimizedBlock::IfBlock { .. } => { unreachable!("IfBlocks should not used directly, but IfElseBlocks instead") }
                 OptimizedBlock::OptimizedAssignament { var_index, value } => {
-                    self.context.variables[*var_index] = OptimizedRuntimeVariable { value: OptimizedVariable::Value(self.context.resolve_value(value.dir, &self.ast)?) }
+                    self.context.variables[*var_index] = OptimizedRuntimeVariable { value: OptimizedVariable::Value(self.context.resolve_value(value.dir, &self.ast, &state)?) }
                 }
                 OptimizedBlock::FnCall(function) => {
-                    function.function.execute_iter(function.args.iter().map(|value_dir| self.context.resolve_value(value_dir, &self.ast)))?;
+                    self.context.execute_function(function, self.ast, &state)?;
                 }
                 OptimizedBlock::ReturnCall(value) => {
-                    let value = self.context.resolve_value(value.dir, &self.ast)?;
+                    let value = self.context.resolve_value(value.dir, &self.ast, &state)?;
                     return Ok(value);
                 }
             }
@@ -275,13 +302,13 @@ impl<'ast> OptimizedASTExecutor<'ast> {
 }
 
 impl OptimizedExecutingContext {
-    fn execute_block(&mut self, block: &OptimizedBlock, ast: &OptimizedAST) -> Result<Option<MoonValue>, RuntimeError> {
+    fn execute_block(&mut self, block: &OptimizedBlock, ast: &OptimizedAST, state: &ExecutionState) -> Result<Option<MoonValue>, RuntimeError> {
         match block {
             OptimizedBlock::WhileBlock { condition, statements } => {
-                while self.resolve_value(condition.dir, ast)?.try_into()
+                while self.resolve_value(condition.dir, ast, state)?.try_into()
                     .map_err(|_| RuntimeError::CannotTurnPredicateToBool { type_of_statement: "if", function_error_message: "".to_string() })? {
                     for statement in statements.iter().map(|block_index| &ast.blocks[block_index]) {
-                        if let Some(res) = self.execute_block(statement, ast)? {
+                        if let Some(res) = self.execute_block(statement, ast, state)? {
                             return Ok(Some(res));
                         }
                     }
@@ -292,10 +319,10 @@ impl OptimizedExecutingContext {
                 for if_block_dir in blocks.iter() {
                     match &ast.blocks[if_block_dir] {
                         OptimizedBlock::IfBlock { condition, statements } => {
-                            if self.resolve_value(condition.dir, ast)?.try_into()
+                            if self.resolve_value(condition.dir, ast, state)?.try_into()
                                 .map_err(|_| RuntimeError::CannotTurnPredicateToBool { type_of_statement: "if", function_error_message: "".to_string() })? {
                                 for statement in statements.iter().map(|block_index| &ast.blocks[block_index]) {
-                                    if let Some(res) = self.execute_block(statement, ast)? {
+                                    if let Some(res) = self.execute_block(statement, ast, state)? {
                                         return Ok(Some(res));
                                     }
                                 }
@@ -307,19 +334,30 @@ impl OptimizedExecutingContext {
                 }
             }
             OptimizedBlock::OptimizedAssignament { var_index, value } =>
-                self.variables[*var_index] = OptimizedRuntimeVariable { value: OptimizedVariable::Value(self.resolve_value(value.dir, ast)?) },
+                self.variables[*var_index] = OptimizedRuntimeVariable { value: OptimizedVariable::Value(self.resolve_value(value.dir, ast, state)?) },
             OptimizedBlock::FnCall(function) => {
-                function.function.execute_iter(function.args.iter().map(|value_dir| self.resolve_value(value_dir, ast)))?;
+                self.execute_function(function, ast, state)?;
             }
             OptimizedBlock::ReturnCall(value) => {
-                let value = self.resolve_value(value.dir, ast)?;
+                let value = self.resolve_value(value.dir, ast, state)?;
                 return Ok(Some(value));
             }
         }
         Ok(None)
     }
 
-    fn resolve_value(&mut self, value_dir: usize, ast: &OptimizedAST) -> Result<MoonValue, RuntimeError> {
+    fn execute_function(&mut self, function: &OptimizedASTFunction, ast: &OptimizedAST, state: &ExecutionState) -> Result<MoonValue, RuntimeError> {
+        let value = function.function.execute_iter_with_state(function.args.iter().map(|value_dir| self.resolve_value(value_dir, ast, state)), state)?;
+        let (mutated_argument, result) = function.function.split_mutation(value);
+        if let Some(mutated_argument) = mutated_argument {
+            if let Some(OptimizedFullValue::DirectVariable(variable_index)) = function.args.iter().next().map(|value_dir| &ast.values[value_dir]) {
+                self.variables[*variable_index] = OptimizedRuntimeVariable { value: OptimizedVariable::Value(mutated_argument) };
+            }
+        }
+        Ok(result)
+    }
+
+    fn resolve_value(&mut self, value_dir: usize, ast: &OptimizedAST, state: &ExecutionState) -> Result<MoonValue, RuntimeError> {
         Ok(match &ast.values[value_dir] {
             OptimizedFullValue::Null => MoonValue::Null,
             OptimizedFullValue::Boolean(v) => MoonValue::Boolean(v.clone()),
@@ -328,29 +366,34 @@ impl OptimizedExecutingContext {
             OptimizedFullValue::String(v) => MoonValue::String(v.clone()),
             OptimizedFullValue::Array(v) => {
                 let mut res = Vec::with_capacity(v.len);
-                for value in v.iter().map(|value_dir| self.resolve_value(value_dir, ast)) {
+                for value in v.iter().map(|value_dir| self.resolve_value(value_dir, ast, state)) {
                     res.push(value?)
                 }
                 MoonValue::Array(res)
             }
-            OptimizedFullValue::Function(function) => {
-                function.function.execute_iter(function.args.iter()
-                    .map(|value_dir| self.resolve_value(value_dir, ast)))?
+            OptimizedFullValue::Map(v) => {
+                let mut res = Vec::with_capacity(v.len / 2);
+                let mut entries = v.iter();
+                while let (Some(key_dir), Some(value_dir)) = (entries.next(), entries.next()) {
+                    res.push((self.resolve_value(key_dir, ast, state)?, self.resolve_value(value_dir, ast, state)?));
+                }
+                MoonValue::Map(res)
             }
+            OptimizedFullValue::Function(function) => self.execute_function(function, ast, state)?,
             OptimizedFullValue::DirectVariable(variable_index) => {
-                self.resolve_variable(ast, *variable_index)?
+                self.resolve_variable(ast, *variable_index, state)?
             }
         })
     }
 
-    fn resolve_variable(&mut self, ast: &OptimizedAST, variable_index: usize) -> Result<MoonValue, RuntimeError> {
+    fn resolve_variable(&mut self, ast: &OptimizedAST, variable_index: usize, state: &ExecutionState) -> Result<MoonValue, RuntimeError> {
         let mut should_inline = true;
         let value = match &self.variables[variable_index].value {
             OptimizedVariable::Value(value) => {
                 should_inline = false;
                 value.clone()
             }
-            OptimizedVariable::ASTValue(value_dir) => { self.resolve_value(value_dir.dir, ast)? }
+            OptimizedVariable::ASTValue(value_dir) => { self.resolve_value(value_dir.dir, ast, state)? }
         };
         if should_inline {
             self.variables[variable_index].value = OptimizedVariable::Value(value.clone());
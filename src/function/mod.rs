@@ -1,10 +1,18 @@
 use alloc::fmt::{Debug, Formatter};
-use alloc::string::ToString;
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
 use alloc::sync::Arc;
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::any::Any;
+use core::cell::{Cell, RefCell};
 
 use paste::paste;
 
 use crate::execution::RuntimeError;
+use crate::parsing::MoonValueKind;
+use crate::reduced_value_impl::impl_std_random;
+use crate::reduced_value_impl::{CoercionPolicy, ConversionError};
 use crate::value::MoonValue;
 
 /// Allows to turn values to [crate::function::MoonFunction], but you shouldn't try to implement
@@ -18,15 +26,66 @@ pub trait ToAbstractFunction<Params, Return, Function, Dummy> {
     fn dummy(_params: Params, _return_value: Return, _dummy: Dummy) {}
 }
 
+/// Return value of the functions created with [crate::FunctionDefinition::new_mutating].
+pub struct Mutation<Argument, Output> {
+    /// New value of the first argument, written back into the variable it was read from.
+    pub argument: Argument,
+    /// Value the call results in.
+    pub result: Output,
+}
+
+impl<Argument: Into<MoonValue>, Output: Into<MoonValue>> From<Mutation<Argument, Output>> for MoonValue {
+    fn from(mutation: Mutation<Argument, Output>) -> Self {
+        MoonValue::Array(vec![mutation.argument.into(), mutation.result.into()])
+    }
+}
+
+/// State of an execution given to the functions it calls, it holds the state of the random number
+/// generator of the `random` module, see [crate::ASTExecutor::seed_random], and the state of the
+/// host, see [crate::ASTExecutor::execute_with_state], functions whose first parameter is
+/// `&mut T` or `&T` borrow the latter once their arguments are converted, so the arguments can
+/// call them too.
+pub(crate) struct ExecutionState<'host> {
+    random: Cell<u64>,
+    host: RefCell<Option<&'host mut dyn Any>>,
+}
+
+impl<'host> ExecutionState<'host> {
+    pub(crate) fn new(random: u64, host: Option<&'host mut dyn Any>) -> Self {
+        Self { random: Cell::new(random), host: RefCell::new(host) }
+    }
+}
+
+impl Default for ExecutionState<'_> {
+    fn default() -> Self {
+        Self::new(impl_std_random::DEFAULT_SEED, None)
+    }
+}
+
 #[derive(Clone)]
 pub struct MoonFunction {
-    function: Arc<dyn Fn(&mut dyn Iterator<Item=Result<MoonValue, RuntimeError>>) -> Result<MoonValue, RuntimeError> + Send + Sync>,
+    function: Arc<dyn Fn(&mut dyn Iterator<Item=Result<MoonValue, RuntimeError>>, &ExecutionState) -> Result<MoonValue, RuntimeError> + Send + Sync>,
     number_of_params: usize,
+    mutates_first_argument: bool,
+    name: Option<Arc<str>>,
+    parameters: Option<Arc<[Parameter]>>,
+}
+
+/// Rust type of a parameter of a function, along with the name of the type scripts know it by and
+/// the kind of value it's built from, if it's one of the built-in kinds.
+type Parameter = (&'static str, Option<String>, Option<MoonValueKind<'static>>);
+
+/// Describes a parameter of the type `T`, used to check arguments against a [CoercionPolicy] and
+/// to tell overloads apart.
+fn parameter_of<T>() -> Parameter {
+    let type_name = MoonValueKind::get_kind_string_of::<T>();
+    let kind = type_name.as_deref().and_then(MoonValueKind::built_in_kind_of);
+    (core::any::type_name::<T>(), type_name, kind)
 }
 
 impl PartialEq for MoonFunction{
     fn eq(&self, other: &Self) -> bool {
-        self.number_of_params == other.number_of_params
+        self.number_of_params == other.number_of_params && self.mutates_first_argument == other.mutates_first_argument
     }
 }
 
@@ -35,19 +94,244 @@ impl Debug for MoonFunction {
         f.debug_struct("MoonFunction")
             .field("Function", &Arc::as_ptr(&self.function))
             .field("Number of params", &self.number_of_params)
+            .field("Mutates first argument", &self.mutates_first_argument)
             .finish()
     }
 }
 
 impl MoonFunction {
+    /// Creates a function that receives its arguments as they are, being able to decide how many
+    /// of them are resolved.
+    pub(crate) fn from_fn<Function>(number_of_params: usize, function: Function) -> Self
+    where Function: Fn(&mut dyn Iterator<Item=Result<MoonValue, RuntimeError>>) -> Result<MoonValue, RuntimeError> + Send + Sync + 'static {
+        Self::from_fn_with_state(number_of_params, move |values, _| function(values))
+    }
+
+    /// Same as [Self::from_fn], but the function also receives the state of the execution, which
+    /// it has to give to the functions it calls.
+    pub(crate) fn from_fn_with_state<Function>(number_of_params: usize, function: Function) -> Self
+    where Function: Fn(&mut dyn Iterator<Item=Result<MoonValue, RuntimeError>>, &ExecutionState) -> Result<MoonValue, RuntimeError> + Send + Sync + 'static {
+        Self { function: Arc::new(function), number_of_params, mutates_first_argument: false, name: None, parameters: None }
+    }
+
+    /// Gives a name to this function, which is used to tell which function failed to convert its
+    /// arguments on [RuntimeError::CannotParseArgument].
+    pub(crate) fn named(mut self, name: &str) -> Self {
+        self.name = Some(Arc::from(name));
+        self
+    }
+
+    /// Fills the name of the function on errors created when converting its arguments, as the
+    /// conversion doesn't know the name of the function it belongs to.
+    fn name_error(&self, error: RuntimeError) -> RuntimeError {
+        match (error, &self.name) {
+            (RuntimeError::CannotParseArgument { function_name, argument_position, expected_type, error }, Some(name))
+            if function_name.is_empty() => RuntimeError::CannotParseArgument { function_name: name.to_string(), argument_position, expected_type, error },
+            (RuntimeError::StateNotGiven { function_name, expected_type }, Some(name))
+            if function_name.is_empty() => RuntimeError::StateNotGiven { function_name: name.to_string(), expected_type },
+            (error, _) => error,
+        }
+    }
+
+    /// Marks this function as one that mutates its first argument, these functions must return a
+    /// [MoonValue::Array] with two values, the mutated first argument and the actual result, so
+    /// the mutated value can be written back into the variable it was read from.
+    pub(crate) fn mutating(mut self) -> Self {
+        self.mutates_first_argument = true;
+        self
+    }
+
+    pub(crate) fn number_of_params(&self) -> usize {
+        self.number_of_params
+    }
+
+    /// Names of the types scripts know the parameters of this function by, if it's known which
+    /// they are, a parameter without name receives any value, like [MoonValue].
+    pub(crate) fn parameter_type_names(&self) -> Option<Vec<Option<&str>>> {
+        self.parameters.as_ref()
+            .map(|parameters| parameters.iter().map(|(_, type_name, _)| type_name.as_deref()).collect())
+    }
+
+    /// Creates a function running the first of these overloads that receives as many arguments as
+    /// it's called with and that can convert them into its parameters, failing with the error of
+    /// the last one tried if none can.
+    ///
+    /// If any of the overloads mutates its first argument, so does this function, giving back the
+    /// first argument as it was when an overload that doesn't mutate it is the one run.
+    pub(crate) fn dispatching(name: &str, overloads: Vec<MoonFunction>) -> Self {
+        let mutates_first_argument = overloads.iter().any(|overload| overload.mutates_first_argument);
+        let number_of_params = overloads.iter().map(|overload| overload.number_of_params).max().unwrap_or_default();
+        let name: Arc<str> = Arc::from(name);
+        let function_name = name.clone();
+        Self {
+            mutates_first_argument,
+            name: Some(name),
+            ..Self::from_fn_with_state(number_of_params, move |values, state| {
+                let values = values.collect::<Result<Vec<_>, _>>()?;
+                let mut error = RuntimeError::FunctionError {
+                    function_error_message: format!("No overload of '{function_name}' receives {} arguments", values.len()),
+                };
+                for overload in overloads.iter().filter(|overload| overload.number_of_params == values.len()) {
+                    match overload.execute_iter_with_state(values.iter().cloned().map(Ok), state) {
+                        Err(overload_error @ RuntimeError::CannotParseArgument { .. }) => error = overload_error,
+                        Ok(result) if mutates_first_argument && !overload.mutates_first_argument => {
+                            let first_argument = values.first().cloned().unwrap_or(MoonValue::Null);
+                            return Ok(MoonValue::Array(vec![first_argument, result]));
+                        }
+                        result => return result,
+                    }
+                }
+                Err(error)
+            })
+        }
+    }
+
+    pub(crate) fn mutates_first_argument(&self) -> bool {
+        self.mutates_first_argument
+    }
+
+    /// Splits the value returned by this function into its mutated first argument, if this is a
+    /// mutating function, and its actual result.
+    pub(crate) fn split_mutation(&self, value: MoonValue) -> (Option<MoonValue>, MoonValue) {
+        if !self.mutates_first_argument {
+            return (None, value);
+        }
+        match value {
+            MoonValue::Array(mut pair) if pair.len() == 2 => {
+                let result = pair.pop().unwrap();
+                (pair.pop(), result)
+            }
+            value => (None, value),
+        }
+    }
+
+    /// Wraps this function so it returns [MoonValue::Null] without being called if its first
+    /// argument is [MoonValue::Null], the rest of arguments aren't resolved in said case.
+    pub(crate) fn short_circuiting_on_null(&self) -> Self {
+        let function = self.function.clone();
+        Self {
+            mutates_first_argument: self.mutates_first_argument,
+            name: self.name.clone(),
+            ..Self::from_fn_with_state(self.number_of_params, move |values, state| {
+                let first_value = values.next().ok_or(RuntimeError::AnArgumentIsMissing)??;
+                if first_value == MoonValue::Null {
+                    return Ok(MoonValue::Null);
+                }
+                function(&mut core::iter::once(Ok(first_value)).chain(values), state)
+            })
+        }
+    }
+
+    /// Wraps this function so it receives all of its arguments packed in a single
+    /// [MoonValue::Array], this is used on calls where some arguments are spread, failing if the
+    /// array doesn't have as many values as the function has parameters.
+    pub(crate) fn spreading_arguments(&self) -> Self {
+        let (function, number_of_params) = (self.function.clone(), self.number_of_params);
+        Self {
+            mutates_first_argument: self.mutates_first_argument,
+            name: self.name.clone(),
+            ..Self::from_fn_with_state(1, move |values, state| {
+                match values.next().ok_or(RuntimeError::AnArgumentIsMissing)?? {
+                    MoonValue::Array(arguments) if arguments.len() < number_of_params => Err(RuntimeError::AnArgumentIsMissing),
+                    MoonValue::Array(arguments) if arguments.len() > number_of_params =>
+                        Err(RuntimeError::TooManyArguments { expected: number_of_params, found: arguments.len() }),
+                    MoonValue::Array(arguments) => function(&mut arguments.into_iter().map(Ok), state),
+                    value => Err(RuntimeError::CannotSpread { value }),
+                }
+            })
+        }
+    }
+
+    /// Wraps this function so it receives the state of the random number generator of the
+    /// execution as its first argument, this function must return a [MoonValue::Array] with the
+    /// new state and the actual result, the new state is written back into the execution, so
+    /// scripts call it without said argument.
+    pub(crate) fn receiving_random_state(&self) -> Self {
+        let function = self.function.clone();
+        Self {
+            function: Arc::new(move |values, state| {
+                let random_state = MoonValue::Integer(state.random.get() as i128);
+                let values = values.map(|value| value.map_err(|error| shift_argument_position(error, |position| position + 1)));
+                let result = function(&mut core::iter::once(Ok(random_state)).chain(values), state)
+                    .map_err(|error| shift_argument_position(error, |position| position - 1))?;
+                match result {
+                    MoonValue::Array(mut values) if values.len() == 2 => {
+                        let result = values.pop().unwrap();
+                        if let MoonValue::Integer(random_state) = values[0] {
+                            state.random.set(random_state as u64);
+                        }
+                        Ok(result)
+                    }
+                    result => Ok(result),
+                }
+            }),
+            number_of_params: self.number_of_params - 1,
+            parameters: self.parameters.as_ref().map(|parameters| parameters.iter().skip(1).cloned().collect()),
+            ..self.clone()
+        }
+    }
+
+    /// Wraps this function so the arguments it receives are rejected if converting them into its
+    /// parameters requires a coercion the policy doesn't allow, functions that receive their
+    /// arguments as they are, or a lenient policy, leave the function untouched.
+    pub(crate) fn with_coercion_policy(&self, coercion_policy: CoercionPolicy) -> Self {
+        let Some(parameters) = self.parameters.clone().filter(|_| coercion_policy != CoercionPolicy::Lenient) else {
+            return self.clone();
+        };
+        let function = self.function.clone();
+        Self {
+            function: Arc::new(move |values, state| {
+                function(&mut values.enumerate().map(|(index, value)| {
+                    let value = value?;
+                    match parameters.get(index) {
+                        Some((expected_type, _, Some(kind))) if !coercion_policy.allows(kind, &value) => Err(RuntimeError::CannotParseArgument {
+                            function_name: String::new(),
+                            argument_position: index + 1,
+                            expected_type,
+                            error: Box::new(ConversionError::new(kind.clone(), value)),
+                        }),
+                        _ => Ok(value),
+                    }
+                }), state)
+            }),
+            ..self.clone()
+        }
+    }
+
     #[inline]
-    pub(crate) fn execute_iter<'values, ValuesIter>(&self, mut values: ValuesIter) -> Result<MoonValue, RuntimeError> where ValuesIter: Iterator<Item=Result<MoonValue, RuntimeError>> {
-        (self.function)(&mut values)
+    pub(crate) fn execute_iter<'values, ValuesIter>(&self, values: ValuesIter) -> Result<MoonValue, RuntimeError> where ValuesIter: Iterator<Item=Result<MoonValue, RuntimeError>> {
+        self.execute_iter_with_state(values, &ExecutionState::default())
     }
 
     #[inline]
     pub(crate) fn execute_into_iter<'values, ValuesIter>(&self, values: ValuesIter) -> Result<MoonValue, RuntimeError> where ValuesIter: IntoIterator<Item=Result<MoonValue, RuntimeError>> {
-        (self.function)(&mut values.into_iter())
+        self.execute_iter_with_state(values.into_iter(), &ExecutionState::default())
+    }
+
+    #[inline]
+    pub(crate) fn execute_iter_with_state<ValuesIter>(&self, mut values: ValuesIter, state: &ExecutionState) -> Result<MoonValue, RuntimeError> where ValuesIter: Iterator<Item=Result<MoonValue, RuntimeError>> {
+        (self.function)(&mut values, state).map_err(|error| self.name_error(error))
+    }
+}
+
+/// Borrows the state of the host as the type a function receives, failing if it wasn't given or
+/// it's of another type.
+fn with_host_state<TState: Any, TReturn>(state: &ExecutionState, function: impl FnOnce(&mut TState) -> TReturn) -> Result<TReturn, RuntimeError> {
+    let mut host = state.host.borrow_mut();
+    let host = host.as_deref_mut()
+        .and_then(|host| host.downcast_mut::<TState>())
+        .ok_or_else(|| RuntimeError::StateNotGiven { function_name: String::new(), expected_type: core::any::type_name::<TState>() })?;
+    Ok(function(host))
+}
+
+/// Moves the position of the argument a [RuntimeError::CannotParseArgument] of an unnamed function
+/// refers to, errors of the functions called to resolve the arguments already have a name, so they
+/// are left untouched.
+fn shift_argument_position(error: RuntimeError, shift: fn(usize) -> usize) -> RuntimeError {
+    match error {
+        RuntimeError::CannotParseArgument { function_name, argument_position, expected_type, error } if function_name.is_empty() =>
+            RuntimeError::CannotParseArgument { function_name, argument_position: shift(argument_position), expected_type, error },
+        error => error,
     }
 }
 
@@ -56,7 +340,7 @@ macro_rules! impl_to_wrapped_function {
         paste!{
             impl<$($param_names, [<Error $param_names>], )* TReturn, TFunction, TError: ToString,>
                 ToAbstractFunction<($($param_names,)*), TReturn, TFunction, u8> for TFunction
-                where $($param_names: TryFrom<MoonValue, Error=[<Error $param_names>] > + 'static,)*
+                where $($param_names: TryFrom<MoonValue, Error=[<Error $param_names>] > + 'static, [<Error $param_names>]: Into<ConversionError>,)*
                       TReturn: Into<MoonValue> + 'static,
                       TFunction: Fn($($param_names),*) -> Result<TReturn,TError> + 'static + Send + Sync
             {
@@ -64,23 +348,33 @@ macro_rules! impl_to_wrapped_function {
                 #[allow(unused)]
                 fn abstract_function(self) -> MoonFunction {
                     MoonFunction {
-                        function: Arc::new(move |values| {
-                            $(let paste::item!{[<$param_names:lower>]}  = <$param_names>::try_from(values.next()
+                        function: Arc::new(move |values, _| {
+                            let mut argument_position = 0;
+                            $(argument_position += 1;
+                            let paste::item!{[<$param_names:lower>]}  = <$param_names>::try_from(values.next()
                                 .ok_or_else(|| RuntimeError::AnArgumentIsMissing{} )??)
-                                .map_err(|_| RuntimeError::CannotParseArgument{} )?;)*
+                                .map_err(|error| RuntimeError::CannotParseArgument {
+                                    function_name: String::new(),
+                                    argument_position,
+                                    expected_type: core::any::type_name::<$param_names>(),
+                                    error: Box::new(error.into()),
+                                })?;)*
 
                             self($( paste::item!{[<$param_names:lower>]}  ),*)
                                 .map(|return_value| return_value.into())
                                 .map_err(|err| RuntimeError::FunctionError{ function_error_message:err.to_string() })
                         }),
                         number_of_params: $params_len,
+                        mutates_first_argument: false,
+                        name: None,
+                        parameters: Some(Arc::from([$(parameter_of::<$param_names>()),*])),
                     }
                 }
             }
 
             impl<$($param_names, [<Error $param_names>], )* TReturn, TFunction>
                 ToAbstractFunction<($($param_names,)*), TReturn, TFunction, u16> for TFunction
-                where $($param_names: TryFrom<MoonValue, Error=[<Error $param_names>]> + 'static,)*
+                where $($param_names: TryFrom<MoonValue, Error=[<Error $param_names>]> + 'static, [<Error $param_names>]: Into<ConversionError>,)*
                       TReturn: Into<MoonValue> + 'static,
                       TFunction: Fn($($param_names),*) -> TReturn + 'static + Send + Sync
             {
@@ -88,16 +382,98 @@ macro_rules! impl_to_wrapped_function {
                 #[allow(unused)]
                 fn abstract_function(self) -> MoonFunction {
                     MoonFunction {
-                        function: Arc::new(move |values| {
-                            $(let paste::item!{[<$param_names:lower>]}  = <$param_names>::try_from(values.next()
+                        function: Arc::new(move |values, _| {
+                            let mut argument_position = 0;
+                            $(argument_position += 1;
+                            let paste::item!{[<$param_names:lower>]}  = <$param_names>::try_from(values.next()
                                 .ok_or_else(|| RuntimeError::AnArgumentIsMissing{} )??)
-                                .map_err(|_| RuntimeError::CannotParseArgument{} )?;)*
+                                .map_err(|error| RuntimeError::CannotParseArgument {
+                                    function_name: String::new(),
+                                    argument_position,
+                                    expected_type: core::any::type_name::<$param_names>(),
+                                    error: Box::new(error.into()),
+                                })?;)*
 
                             Ok(self($( paste::item!{[<$param_names:lower>]}  ),*)
 
                             .into())
                         }),
                         number_of_params: $params_len,
+                        mutates_first_argument: false,
+                        name: None,
+                        parameters: Some(Arc::from([$(parameter_of::<$param_names>()),*])),
+                    }
+                }
+            }
+        }
+        impl_to_wrapped_function!{state { n: $params_len reference: (mut) dummies: u32 u64 names: $($param_names)* }}
+        impl_to_wrapped_function!{state { n: $params_len reference: () dummies: i32 i64 names: $($param_names)* }}
+    };
+
+    (state { n: $params_len:literal reference: ($($mutability:tt)?) dummies: $result_dummy:ident $plain_dummy:ident names: $($param_names:ident)* }) => {
+        paste!{
+            impl<TState: Any, $($param_names, [<Error $param_names>], )* TReturn, TFunction, TError: ToString,>
+                ToAbstractFunction<(&'static $($mutability)? TState, $($param_names,)*), TReturn, TFunction, $result_dummy> for TFunction
+                where $($param_names: TryFrom<MoonValue, Error=[<Error $param_names>] > + 'static, [<Error $param_names>]: Into<ConversionError>,)*
+                      TReturn: Into<MoonValue> + 'static,
+                      TFunction: Fn(&$($mutability)? TState, $($param_names),*) -> Result<TReturn,TError> + 'static + Send + Sync
+            {
+                #[allow(unused_mut)]
+                #[allow(unused)]
+                fn abstract_function(self) -> MoonFunction {
+                    MoonFunction {
+                        function: Arc::new(move |values, state| {
+                            let mut argument_position = 0;
+                            $(argument_position += 1;
+                            let paste::item!{[<$param_names:lower>]}  = <$param_names>::try_from(values.next()
+                                .ok_or_else(|| RuntimeError::AnArgumentIsMissing{} )??)
+                                .map_err(|error| RuntimeError::CannotParseArgument {
+                                    function_name: String::new(),
+                                    argument_position,
+                                    expected_type: core::any::type_name::<$param_names>(),
+                                    error: Box::new(error.into()),
+                                })?;)*
+
+                            with_host_state(state, |host: &mut TState| self(host, $( paste::item!{[<$param_names:lower>]}  ),*))?
+                                .map(|return_value| return_value.into())
+                                .map_err(|err| RuntimeError::FunctionError{ function_error_message:err.to_string() })
+                        }),
+                        number_of_params: $params_len,
+                        mutates_first_argument: false,
+                        name: None,
+                        parameters: Some(Arc::from([$(parameter_of::<$param_names>()),*])),
+                    }
+                }
+            }
+
+            impl<TState: Any, $($param_names, [<Error $param_names>], )* TReturn, TFunction>
+                ToAbstractFunction<(&'static $($mutability)? TState, $($param_names,)*), TReturn, TFunction, $plain_dummy> for TFunction
+                where $($param_names: TryFrom<MoonValue, Error=[<Error $param_names>]> + 'static, [<Error $param_names>]: Into<ConversionError>,)*
+                      TReturn: Into<MoonValue> + 'static,
+                      TFunction: Fn(&$($mutability)? TState, $($param_names),*) -> TReturn + 'static + Send + Sync
+            {
+                #[allow(unused_mut)]
+                #[allow(unused)]
+                fn abstract_function(self) -> MoonFunction {
+                    MoonFunction {
+                        function: Arc::new(move |values, state| {
+                            let mut argument_position = 0;
+                            $(argument_position += 1;
+                            let paste::item!{[<$param_names:lower>]}  = <$param_names>::try_from(values.next()
+                                .ok_or_else(|| RuntimeError::AnArgumentIsMissing{} )??)
+                                .map_err(|error| RuntimeError::CannotParseArgument {
+                                    function_name: String::new(),
+                                    argument_position,
+                                    expected_type: core::any::type_name::<$param_names>(),
+                                    error: Box::new(error.into()),
+                                })?;)*
+
+                            Ok(with_host_state(state, |host: &mut TState| self(host, $( paste::item!{[<$param_names:lower>]}  ),*))?.into())
+                        }),
+                        number_of_params: $params_len,
+                        mutates_first_argument: false,
+                        name: None,
+                        parameters: Some(Arc::from([$(parameter_of::<$param_names>()),*])),
                     }
                 }
             }
@@ -18,16 +18,25 @@
 //! - medium_functions: Functions added to an Engine can be up to 16 parameters, instead of 8.
 //! - big_functions: Functions added to an Engine can be up to 24 parameters, instead of 8.
 //! - massive_functions: Functions added to an Engine can be up to 40 parameters, instead of 8.
+//! - json: Values can be turned into JSON and back, and the `json` module can be added to an Engine.
+//! - serde: MoonValue implements Serialize and Deserialize, and any serde type can be turned into a
+//!   MoonValue and back through to_value and from_value.
+//! - derive: Adds derive(MoonType), turning structs into MoonValues and back and registering their
+//!   public fields as properties.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
 extern crate alloc;
 extern crate core;
 extern crate pest;
+#[cfg(feature = "derive")]
+extern crate self as moon_script;
 
 pub use engine::context::ContextBuilder;
 pub use engine::context::InputVariable;
+pub use engine::docs::DocsFormat;
 pub use engine::Constant;
+pub use engine::OperatorError;
 pub use engine::Engine;
 
 pub use execution::ast::ASTExecutor;
@@ -37,30 +46,41 @@ pub use execution::RuntimeError;
 pub use execution::optimized_ast::OptimizedAST;
 pub use execution::optimized_ast::OptimizedASTExecutor;
 
+pub use function::Mutation;
 pub use function::ToAbstractFunction;
 
 pub use parsing::error::ASTBuildingError;
 pub use parsing::error::ParsingError;
 pub use parsing::FunctionDefinition;
+pub use parsing::RegisteredFunction;
 pub use parsing::MoonValueKind;
 
 pub use value::MoonValue;
+pub use reduced_value_impl::ConversionError;
+pub use reduced_value_impl::CoercionPolicy;
+pub use reduced_value_impl::impl_operators::{ArithmeticError, OverflowPolicy};
+#[cfg(feature = "json")]
+pub use reduced_value_impl::impl_std_json::{JsonError, MAX_JSON_DEPTH};
+#[cfg(feature = "serde")]
+pub use reduced_value_impl::impl_serde::{from_value, to_value, SerdeError};
+#[cfg(feature = "derive")]
+pub use moon_script_derive::MoonType;
 
 
-#[cfg(feature = "std")]
-type HashSet<T> = std::collections::HashSet<T>;
 #[cfg(feature = "std")]
 type HashMap<K, V> = std::collections::HashMap<K, V>;
 #[cfg(feature = "std")]
+type HashSet<K> = std::collections::HashSet<K>;
+#[cfg(feature = "std")]
 type LazyLock<T> = std::sync::LazyLock<T>;
 
 
 #[cfg(not(feature = "std"))]
 type HashMap<K, V> = alloc::collections::BTreeMap<K, V>;
 #[cfg(not(feature = "std"))]
-type LazyLock<T> = lazy_lock::LazyLock<T>;
+type HashSet<K> = alloc::collections::BTreeSet<K>;
 #[cfg(not(feature = "std"))]
-type HashSet<T> = alloc::collections::btree_set::BTreeSet<T>;
+type LazyLock<T> = lazy_lock::LazyLock<T>;
 
 
 pub mod engine;
@@ -77,9 +97,15 @@ pub(crate) mod lazy_lock;
 
 #[cfg(test)]
 mod test {
+    use alloc::format;
+    use alloc::string::{String, ToString};
+    use alloc::sync::Arc;
+    use alloc::vec;
+    use alloc::vec::Vec;
+
     use crate::engine::context::ContextBuilder;
-    use crate::engine::Engine;
-    use crate::{FunctionDefinition, InputVariable};
+    use crate::engine::{Constant, Engine, OperatorError};
+    use crate::{ArithmeticError, CoercionPolicy, DocsFormat, ConversionError, FunctionDefinition, InputVariable, MoonValue, MoonValueKind, OverflowPolicy, RuntimeError};
     use log::Level;
 
     #[cfg(feature = "std")]
@@ -190,6 +216,956 @@ mod test {
         assert_eq!(false, res);
     }
 
+    #[test]
+    fn test_built_in_associated_functions() {
+        let mut engine = Engine::default();
+        engine.add_function(FunctionDefinition::new("double", |int: i128| int * 2)
+            .associated_type_name("int"));
+
+        let context = ContextBuilder::new()
+            .with_variable(InputVariable::new("name").associated_type_of::<String>());
+        let ast = engine.parse(r#"
+            let text = "a, b ,c";
+            let parts = text.split(",");
+            let last = parts[2];
+            let number = -21;
+            return [name.len(), parts.len(), last.trim(), number.abs().double(), parts.contains("a")];
+        "#, context).unwrap();
+        let result = ast.executor().push_variable("name", "Jorge").execute().unwrap();
+
+        assert_eq!(MoonValue::from([MoonValue::from(5), MoonValue::from(3), MoonValue::from("c"),
+                                       MoonValue::from(42), MoonValue::from(true)]), result);
+    }
+
+    #[test]
+    fn test_null_coalescing_and_optional_chaining() {
+        let mut engine = Engine::default();
+        engine.add_function(FunctionDefinition::new("get_target", |player: i32| (player > 0).then_some(player))
+            .associated_type_name("player").known_return_type_name("enemy"));
+        engine.add_function(FunctionDefinition::new("get_health", |enemy: i32| enemy * 10)
+            .associated_type_name("enemy"));
+
+        let context = ContextBuilder::new()
+            .with_variable(InputVariable::new("player").associated_type("player"));
+        let ast = engine.parse("player.target?.health ?? -1", context).unwrap();
+        let optimized_ast = ast.clone().to_optimized_ast();
+
+        for (player, expected_health) in [(3, 30), (0, -1)] {
+            let result: i32 = ast.executor().push_variable("player", player)
+                .execute().unwrap().try_into().unwrap();
+            assert_eq!(expected_health, result);
+            let result: i32 = optimized_ast.executor().push_variable("player", player)
+                .execute().unwrap().try_into().unwrap();
+            assert_eq!(expected_health, result);
+        }
+
+        let constant_result = engine.parse("null ?? 5", ContextBuilder::new())
+            .unwrap().execute().unwrap();
+        assert_eq!(MoonValue::Integer(5), constant_result);
+    }
+
+    #[test]
+    fn test_membership_operators() {
+        let mut engine = Engine::default();
+        let result = engine.parse(r#"
+            let stats = {"hp": 10, mana: 5};
+            return [2 in [1 2 3], "oo" in "moon", "hp" in stats, "mana" in stats, 4 not in [1 2 3], "stamina" not in stats];
+        "#, ContextBuilder::new()).unwrap();
+        assert_eq!(Engine::new().parse("[true, true, true, true, true, true]", ContextBuilder::new()).unwrap(), result);
+        let keys = engine.parse("let hp = 1; {hp: hp, true: 2, (true): 3, 4: 4}", ContextBuilder::new()).unwrap().execute().unwrap();
+        assert_eq!(MoonValue::Map(vec![(MoonValue::from("hp"), MoonValue::from(1)), (MoonValue::from("true"), MoonValue::from(2)),
+                                       (MoonValue::from(true), MoonValue::from(3)), (MoonValue::from(4), MoonValue::from(4))]), keys);
+
+        engine.add_function(FunctionDefinition::new("contains", |inventory: i32, item: String| {
+            item.len() as i32 == inventory
+        }).associated_type_name("inventory"));
+        let context = ContextBuilder::new()
+            .with_variable(InputVariable::new("inventory").associated_type("inventory"));
+        let ast = engine.parse(r#"["sword" in inventory, "axe" not in inventory]"#, context).unwrap();
+        let result = ast.executor().push_variable("inventory", 5).execute().unwrap();
+        assert_eq!(MoonValue::from([true, true]), result);
+    }
+
+    #[test]
+    fn test_destructuring_assignments() {
+        let mut engine = Engine::default();
+        let result = engine.parse(r#"
+            let [x, y] = [3, 4];
+            let {hp, mana} = {"mana": 5, "hp": 10};
+            return [x, y, hp, mana];
+        "#, ContextBuilder::new()).unwrap();
+        assert_eq!(Engine::new().parse("[3, 4, 10, 5]", ContextBuilder::new()).unwrap(), result);
+        assert!(engine.parse("let [x, y] = [3];", ContextBuilder::new()).is_err());
+        assert!(engine.parse("let [x, x] = [3, 4];", ContextBuilder::new()).is_err());
+        assert!(engine.parse("let {hp, hp} = {hp: 1};", ContextBuilder::new()).is_err());
+
+        engine.add_function(FunctionDefinition::new("get_pos", |length: i64| {
+            (0..length).collect::<Vec<_>>()
+        }));
+        let context = ContextBuilder::new().with_variable(InputVariable::new("length"));
+        let ast = engine.parse("let [x, y] = get_pos(length); let [z, w] = get_pos(length); x + y + z + w", context).unwrap();
+        let optimized_ast = ast.clone().to_optimized_ast();
+        assert_eq!(MoonValue::Integer(2), ast.executor().push_variable("length", 2).execute().unwrap());
+        assert_eq!(MoonValue::Integer(2), optimized_ast.executor().push_variable("length", 2).execute().unwrap());
+        assert!(ast.executor().push_variable("length", 3).execute().is_err());
+        assert!(optimized_ast.executor().push_variable("length", 3).execute().is_err());
+    }
+
+    #[test]
+    fn test_spread_values() {
+        let mut engine = Engine::default();
+        engine.add_function(FunctionDefinition::new("sum", |a: i64, b: i64, c: i64| a + b + c).inline());
+        let result = engine.parse(r#"
+            let a = [1, 2];
+            let b = [4];
+            return [[...a, 3, ...b, ...[]], sum(...a, 3), sum(...[1], ...b, 5)];
+        "#, ContextBuilder::new()).unwrap();
+        assert_eq!(Engine::new().parse("[[1, 2, 3, 4], 6, 10]", ContextBuilder::new()).unwrap(), result);
+        assert!(engine.parse("[...5]", ContextBuilder::new()).is_err());
+
+        let context = ContextBuilder::new().with_variable(InputVariable::new("args"));
+        let ast = engine.parse("[...args, sum(...args, 1)]", context).unwrap();
+        let result = ast.executor().push_variable("args", vec![2, 3]).execute().unwrap();
+        assert_eq!(MoonValue::from([2, 3, 6]), result);
+        let result = ast.clone().to_optimized_ast().executor().push_variable("args", vec![2, 3]).execute().unwrap();
+        assert_eq!(MoonValue::from([2, 3, 6]), result);
+        let error = ast.executor().push_variable("args", vec![2, 3, 4]).execute().unwrap_err();
+        assert!(matches!(error, RuntimeError::TooManyArguments { expected: 3, found: 4 }), "{error:?}");
+        let error = ast.executor().push_variable("args", vec![2]).execute().unwrap_err();
+        assert!(matches!(error, RuntimeError::AnArgumentIsMissing), "{error:?}");
+        assert!(engine.parse("sum(...[1, 2, 3, 4])", ContextBuilder::new()).is_err());
+
+        engine.add_function(FunctionDefinition::new("get_damage", |target: i32, strength: i32, multiplier: i32| target * strength * multiplier)
+            .associated_type_name("target"));
+        let context = ContextBuilder::new()
+            .with_variable(InputVariable::new("target").associated_type("target"))
+            .with_variable(InputVariable::new("args"));
+        let ast = engine.parse("target?.damage(...args)", context).unwrap();
+        let result = ast.executor().push_variable("target", MoonValue::Null).push_variable("args", vec![2, 3]).execute().unwrap();
+        assert_eq!(MoonValue::Null, result);
+        let result = ast.executor().push_variable("target", 1).push_variable("args", vec![2, 3]).execute().unwrap();
+        assert_eq!(MoonValue::Integer(6), result);
+    }
+
+    #[test]
+    fn test_assert_statement() {
+        let engine = Engine::default();
+        assert!(engine.parse("assert 1 < 2; 5", ContextBuilder::new()).is_ok());
+        assert!(engine.parse("assert 1 > 2, \"one is not greater than two\";", ContextBuilder::new()).is_err());
+
+        let context = ContextBuilder::new().with_variable(InputVariable::new("hp"));
+        let ast = engine.parse("let max = 100;\nassert hp <= max, \"hp is over \" + max;\nhp", context).unwrap();
+        assert_eq!(MoonValue::Integer(50), ast.executor().push_variable("hp", 50).execute().unwrap());
+        match ast.to_optimized_ast().executor().push_variable("hp", 150).execute() {
+            Err(crate::RuntimeError::AssertionFailed { predicate, message, line, column }) => {
+                assert_eq!("hp <= max", predicate);
+                assert_eq!(Some("hp is over 100".to_string()), message);
+                assert_eq!((2, 8), (line, column));
+            }
+            other => panic!("Expected a failed assertion, but got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_identifiers_and_keywords() {
+        let engine = Engine::default();
+        let result = engine.parse("let _hidden = 1; let vitesse_é = 2; let note = 3; [_hidden, vitesse_é, note]", ContextBuilder::new());
+        assert_eq!(Engine::new().parse("[1, 2, 3]", ContextBuilder::new()).unwrap(), result.unwrap());
+        assert!(engine.parse("let if = 5;", ContextBuilder::new()).is_err());
+        assert!(engine.parse("let [x, return] = [1, 2];", ContextBuilder::new()).is_err());
+
+        let context = ContextBuilder::new().with_variable(InputVariable::new("ñandú").value(7));
+        assert_eq!(MoonValue::Integer(7), engine.parse("ñandú", context).unwrap().execute().unwrap());
+        let context = ContextBuilder::new().with_variable(InputVariable::new("not a name"));
+        assert!(engine.parse("1", context).is_err());
+        let context = ContextBuilder::new().with_variable(InputVariable::new("while"));
+        assert!(engine.parse("1", context).is_err());
+    }
+
+    #[test]
+    fn test_std_string_module() {
+        let engine = Engine::new().with_std_string();
+        let result = engine.parse(r#"
+            let parts = string/split("a-b-c", "-");
+            return [string/len("ñandú"), string/lower("MoOn"), string/join(parts, "+"), string/replace("aXbX", "X", "."),
+                string/starts_with("moon", "mo"), string/find("ñandú", "dú"), string/find("moon", "sun"),
+                string/substring("moonscript", 4, 10), string/repeat("ab", 3), string/pad_left("42", 5, " ")];
+        "#, ContextBuilder::new()).unwrap();
+        let expected = Engine::new().parse(r#"[5, "moon", "a+b+c", "a.b.", true, 3, null, "script", "ababab", "   42"]"#,
+            ContextBuilder::new()).unwrap();
+        assert_eq!(expected, result);
+        assert!(engine.parse(r#"string/substring("moon", 3, 8)"#, ContextBuilder::new()).is_err());
+        assert!(Engine::new().parse(r#"string/upper("moon")"#, ContextBuilder::new()).is_err());
+
+        let context = ContextBuilder::new().with_variable(InputVariable::new("name"));
+        let ast = engine.parse(r#"string/format("Hello {}, you are {}!", [string/upper(name), 30])"#, context).unwrap();
+        let result = ast.executor().push_variable("name", "luna").execute().unwrap();
+        assert_eq!(MoonValue::from("Hello LUNA, you are 30!"), result);
+    }
+
+    #[test]
+    fn test_std_math_module() {
+        let engine = Engine::new().with_std_math();
+        let result = engine.parse(r#"
+            [math/abs(-3), math/abs(-2.5), math/min(4, 2), math/max(1.5, 3), math/clamp(15, 0, 10), math/pow(2, 10),
+                math/pow(4, 0.5), math/floor(1.7), math/ceil(1.2), math/round(2.5), math/sqrt(9.0), math/lerp(10.0, 20.0, 0.25)]
+        "#, ContextBuilder::new()).unwrap();
+        let expected = Engine::new().parse("[3, 2.5, 2, 3.0, 10, 1024, 2.0, 1.0, 2.0, 3.0, 3.0, 12.5]", ContextBuilder::new()).unwrap();
+        assert_eq!(expected, result);
+        assert!(engine.parse("math/pow(10, 100)", ContextBuilder::new()).is_err());
+
+        let context = ContextBuilder::new().with_variable(InputVariable::new("angle"));
+        let ast = engine.parse("math/sin(angle) * math/sin(angle) + math/cos(angle) * math/cos(angle) + math/atan2(0.0, -1.0) - PI", context).unwrap();
+        let result: f64 = ast.executor().push_variable("angle", 0.7).execute().unwrap().try_into().unwrap();
+        assert!((result - 1.0).abs() < 1e-9);
+        assert_eq!(MoonValue::Decimal(core::f64::consts::E), engine.parse("math/e()", ContextBuilder::new()).unwrap().execute().unwrap());
+        assert!(engine.parse("E", ContextBuilder::new()).is_err());
+    }
+
+    #[test]
+    fn test_std_array_module() {
+        let engine = Engine::new().with_std_array();
+        let result = engine.parse(r#"
+            let values = [3, 1, 2];
+            values.push(5);
+            let popped = array/pop(values);
+            array/insert(values, 0, 4);
+            let removed = values.remove(1);
+            values.sort();
+            let reversed = [1, 2, 3];
+            reversed.reverse();
+            return [values, popped, removed, reversed, values.len(), array/contains(values, 4), values.index_of(2),
+                array/index_of(values, 7), array/concat([1], [2, 3]), array/slice([1, 2, 3, 4], 1, 3), array/sum([1, 2, 3]),
+                array/sum([1, 0.5]), array/unique([1, 2, 1, 3, 2])];
+        "#, ContextBuilder::new()).unwrap();
+        let expected = Engine::new().parse("[[1, 2, 4], 5, 3, [3, 2, 1], 3, true, 1, null, [1, 2, 3], [2, 3], 6, 1.5, [1, 2, 3]]",
+            ContextBuilder::new()).unwrap();
+        assert_eq!(expected.execute().unwrap(), result.execute().unwrap());
+        assert!(engine.parse(r#"array/sort([1, "one"])"#, ContextBuilder::new()).is_err());
+        let pushed = Engine::new().parse("let values = [1]; values.push(2); values", ContextBuilder::new()).unwrap();
+        assert_eq!(MoonValue::from([1, 2]), pushed.execute().unwrap());
+
+        let context = ContextBuilder::new().with_variable(InputVariable::new("items").associated_type(MoonValueKind::Array));
+        let ast = engine.parse(r#"
+            items.push("shield");
+            let first = items.remove(0);
+            [first, items]
+        "#, context).unwrap();
+        let expected = MoonValue::from(vec![MoonValue::from("sword"), MoonValue::from(["axe", "shield"])]);
+        assert_eq!(expected, ast.executor().push_variable("items", ["sword", "axe"]).execute().unwrap());
+        assert_eq!(expected, ast.to_optimized_ast().executor().push_variable("items", ["sword", "axe"]).execute().unwrap());
+    }
+
+    #[test]
+    fn test_std_random_module() {
+        let engine = Engine::new().with_std_random();
+        let ast = engine.parse(r#"
+            let rolls = [random/int(1, 6), random/int(1, 6), random/int(1, 6), random/int(1, 6)];
+            [rolls, random/decimal(), random/choice(["sword", "axe", "bow"]), random/shuffle([1, 2, 3, 4, 5])]
+        "#, ContextBuilder::new()).unwrap();
+        let optimized_ast = ast.clone().to_optimized_ast();
+        let result = ast.executor().seed_random(7).execute().unwrap();
+        assert_eq!(result, ast.executor().seed_random(7).execute().unwrap());
+        assert_eq!(result, optimized_ast.executor().seed_random(7).execute().unwrap());
+        assert_ne!(result, ast.executor().seed_random(8).execute().unwrap());
+        assert_eq!(ast.execute().unwrap(), optimized_ast.execute().unwrap());
+
+        let values = match result {
+            MoonValue::Array(values) => values,
+            _ => panic!("Expected an array"),
+        };
+        let rolls: Vec<i64> = values[0].clone().try_into().unwrap();
+        assert!(rolls.iter().all(|roll| (1..=6).contains(roll)));
+        assert!(rolls.windows(2).any(|rolls| rolls[0] != rolls[1]));
+        let decimal: f64 = values[1].clone().try_into().unwrap();
+        assert!((0.0..1.0).contains(&decimal));
+        let mut shuffled: Vec<i64> = values[3].clone().try_into().unwrap();
+        shuffled.sort();
+        assert_eq!(vec![1, 2, 3, 4, 5], shuffled);
+        assert!(engine.parse("random/int(6, 1)", ContextBuilder::new()).unwrap().execute().is_err());
+
+        let spread_ast = engine.parse(r#"
+            let args = [1, 1000];
+            let a = random/int(...args);
+            let b = random/int(...args);
+            return [a, b, random/int(1, 1000), random/int(1, 1000)];
+        "#, ContextBuilder::new()).unwrap();
+        let result: Vec<i64> = spread_ast.executor().seed_random(7).execute().unwrap().try_into().unwrap();
+        assert_ne!(result[0], result[1]);
+        assert!(result.iter().all(|value| (1..=1000).contains(value)));
+        let not_spread: Vec<i64> = engine.parse("return [random/int(1, 1000), random/int(1, 1000)];", ContextBuilder::new())
+            .unwrap().executor().seed_random(7).execute().unwrap().try_into().unwrap();
+        assert_eq!(not_spread, result[..2]);
+        let optimized_result: Vec<i64> = spread_ast.to_optimized_ast().executor().seed_random(7).execute().unwrap().try_into().unwrap();
+        assert_eq!(result, optimized_result);
+        let error = engine.parse(r#"random/int(1, "six")"#, ContextBuilder::new()).unwrap().execute().unwrap_err();
+        assert!(matches!(error, RuntimeError::CannotParseArgument { argument_position: 2, .. }), "{error:?}");
+    }
+
+    #[test]
+    fn test_conversion_errors() {
+        let error = Vec::<u8>::try_from(MoonValue::from([MoonValue::from(1), MoonValue::from("one")])).unwrap_err();
+        assert_eq!(ConversionError { expected: MoonValueKind::Integer, found: MoonValue::from("one"), path: "[1]".to_string() }, error);
+
+        let stats = MoonValue::Map(vec![(MoonValue::from("hp"), MoonValue::from([MoonValue::from(true), MoonValue::Null]))]);
+        let error = alloc::collections::BTreeMap::<String, Vec<bool>>::try_from(stats).unwrap_err();
+        assert_eq!(ConversionError { expected: MoonValueKind::Boolean, found: MoonValue::Null, path: r#"["hp"][1]"#.to_string() }, error);
+
+        let mut engine = Engine::new();
+        engine.add_function(FunctionDefinition::new("damage", |amount: u8, target: String| format!("{target} took {amount}")));
+        let error = engine.parse("damage(5, 3)", ContextBuilder::new()).unwrap().execute().unwrap_err();
+        match error {
+            RuntimeError::CannotParseArgument { function_name, argument_position, expected_type, error } => {
+                assert_eq!("damage", function_name);
+                assert_eq!(2, argument_position);
+                assert_eq!(core::any::type_name::<String>(), expected_type);
+                assert_eq!(ConversionError::new(MoonValueKind::String, MoonValue::Integer(3)), *error);
+            }
+            error => panic!("Unexpected error {error}"),
+        }
+
+        struct Even(i64);
+        impl TryFrom<MoonValue> for Even {
+            type Error = ();
+            fn try_from(value: MoonValue) -> Result<Self, Self::Error> {
+                i64::try_from(value).ok().filter(|value| value % 2 == 0).map(Even).ok_or(())
+            }
+        }
+        engine.add_function(FunctionDefinition::new("half", |value: Even| value.0 / 2));
+        let error = engine.parse("half(5)", ContextBuilder::new()).unwrap().execute().unwrap_err();
+        assert!(matches!(error, RuntimeError::CannotParseArgument { argument_position: 1, .. }), "{error:?}");
+    }
+
+    #[test]
+    fn test_coercion_policy() {
+        assert_eq!(Ok(false), bool::try_from(MoonValue::from("no")));
+        assert_eq!(Ok(true), bool::try_from(MoonValue::from("yes")));
+        assert_eq!(Ok(true), bool::try_from(MoonValue::from("1")));
+
+        let mut engine = Engine::new().with_coercion_policy(CoercionPolicy::NumericOnly);
+        engine.add_function(FunctionDefinition::new("halve", |value: i64| value / 2));
+        let execute = |script: &str, policy: Option<CoercionPolicy>| {
+            let context = ContextBuilder::new().with_variable(InputVariable::new("value"));
+            let context = match policy {
+                Some(policy) => context.with_coercion_policy(policy),
+                None => context,
+            };
+            engine.parse(script, context).map_err(|_| ())
+                .and_then(|ast| ast.executor().push_variable("value", MoonValue::from("8")).execute().map_err(|_| ()))
+        };
+
+        assert_eq!(Ok(MoonValue::Integer(4)), execute("halve(8.0)", None));
+        assert_eq!(Err(()), execute("halve(8.5)", None));
+        assert_eq!(Err(()), execute("halve(value)", None));
+        assert_eq!(Ok(MoonValue::Integer(4)), execute("halve(value)", Some(CoercionPolicy::Lenient)));
+        assert_eq!(Err(()), execute("halve(8.0)", Some(CoercionPolicy::Strict)));
+
+        assert_eq!(Ok(MoonValue::Decimal(3.5)), execute("1 + 2.5", None));
+        assert_eq!(Err(()), execute("1 + 2.5", Some(CoercionPolicy::Strict)));
+        assert_eq!(Err(()), execute("true + 1", None));
+        assert_eq!(Ok(MoonValue::Integer(2)), execute("true + 1", Some(CoercionPolicy::Lenient)));
+        assert_eq!(Ok(MoonValue::String("81".to_string())), execute("value + 1", None));
+
+        assert_eq!(Err(()), execute("if 1 { return 1; } return 0;", None));
+        assert_eq!(Err(()), execute("if value { return 1; } return 0;", None));
+        assert_eq!(Err(()), execute("while value { return 1; } return 0;", None));
+        assert_eq!(Ok(MoonValue::Integer(1)), execute("if value { return 1; } return 0;", Some(CoercionPolicy::Lenient)));
+        assert_eq!(Ok(MoonValue::Integer(1)), execute("if value == \"8\" { return 1; } return 0;", None));
+
+        let engine = Engine::new().with_std_random().with_coercion_policy(CoercionPolicy::Strict);
+        let error = engine.parse("random/int(1, 6.0)", ContextBuilder::new()).unwrap().execute().unwrap_err();
+        assert!(matches!(error, RuntimeError::CannotParseArgument { argument_position: 2, .. }), "{error:?}");
+
+        // The policies of the engine don't depend on the order they are given in
+        let strict_then_wrapping = Engine::new().with_coercion_policy(CoercionPolicy::Strict).with_overflow_policy(OverflowPolicy::Wrapping);
+        let wrapping_then_strict = Engine::new().with_overflow_policy(OverflowPolicy::Wrapping).with_coercion_policy(CoercionPolicy::Strict);
+        for engine in [strict_then_wrapping, wrapping_then_strict] {
+            assert_eq!((CoercionPolicy::Strict, OverflowPolicy::Wrapping), (engine.coercion_policy(), engine.overflow_policy()));
+            assert!(engine.parse("return 1 + 2.5;", ContextBuilder::new()).is_err());
+            let context = ContextBuilder::new().with_variable(InputVariable::new("value"));
+            let ast = engine.parse("return value + 2.5;", context).unwrap();
+            assert!(ast.executor().push_variable("value", 1).execute().is_err());
+            assert_eq!(MoonValue::from(i128::MIN), engine.parse("return 170141183460469231731687303715884105727 + 1;", ContextBuilder::new())
+                .unwrap().execute().unwrap());
+        }
+    }
+
+    #[test]
+    fn test_checked_arithmetic() {
+        let engine = Engine::new();
+        let context = || ContextBuilder::new().with_variable(InputVariable::new("value"));
+        let execute = |engine: &Engine, script: &str, value: MoonValue| engine.parse(script, context()).unwrap()
+            .executor().push_variable("value", value).execute();
+
+        for (script, operation, error) in [
+            ("value + 1", "170141183460469231731687303715884105727 + 1", ArithmeticError::Overflow),
+            ("-value - 2", "-170141183460469231731687303715884105727 - 2", ArithmeticError::Overflow),
+            ("value / 0", "170141183460469231731687303715884105727 / 0", ArithmeticError::DivisionByZero),
+            ("value % 0", "170141183460469231731687303715884105727 % 0", ArithmeticError::DivisionByZero),
+            ("1 << value", "1 << 170141183460469231731687303715884105727", ArithmeticError::InvalidShift),
+        ] {
+            match execute(&engine, script, MoonValue::from(i128::MAX)) {
+                Err(RuntimeError::Arithmetic { operation: found_operation, error: found_error }) => {
+                    assert_eq!((operation, error), (found_operation.as_str(), found_error));
+                }
+                result => panic!("Unexpected result {result:?} of {script}"),
+            }
+        }
+        assert!(engine.parse("5 / 0", ContextBuilder::new()).is_err());
+        assert_eq!(MoonValue::from(6), execute(&engine, "2 + 1 << value", MoonValue::from(1)).unwrap());
+
+        let wrapping = Engine::new().with_overflow_policy(OverflowPolicy::Wrapping);
+        assert_eq!(MoonValue::from(i128::MIN), execute(&wrapping, "value + 1", MoonValue::from(i128::MAX)).unwrap());
+        let saturating = Engine::new().with_overflow_policy(OverflowPolicy::Saturating);
+        assert_eq!(MoonValue::from(i128::MIN), execute(&saturating, "-value - 2", MoonValue::from(i128::MAX)).unwrap());
+        assert_eq!(MoonValue::from(i128::MAX), execute(&saturating, "value * 2", MoonValue::from(i128::MAX)).unwrap());
+        assert!(matches!(execute(&saturating, "value / 0", MoonValue::from(1)), Err(RuntimeError::Arithmetic { .. })));
+
+        match execute(&engine, "value / -1", MoonValue::from(i128::MIN)) {
+            Err(RuntimeError::Arithmetic { operation, error }) => {
+                assert_eq!(("-170141183460469231731687303715884105728 / -1", ArithmeticError::Overflow), (operation.as_str(), error));
+            }
+            result => panic!("Unexpected result {result:?} of value / -1"),
+        }
+        assert_eq!(MoonValue::from(i128::MIN), execute(&wrapping, "value / -1", MoonValue::from(i128::MIN)).unwrap());
+        assert_eq!(MoonValue::from(i128::MAX), execute(&saturating, "value / -1", MoonValue::from(i128::MIN)).unwrap());
+        let optimized_ast = engine.parse("value / -1", context()).unwrap().to_optimized_ast();
+        assert!(matches!(optimized_ast.executor().push_variable("value", i128::MIN).execute(), Err(RuntimeError::Arithmetic { .. })));
+        assert_eq!(MoonValue::from(9007199254740993_i64), execute(&engine, "value / 1", MoonValue::from(9007199254740993_i64)).unwrap());
+        assert_eq!(MoonValue::from(3.5), execute(&engine, "value / 2", MoonValue::from(7)).unwrap());
+
+        // Decimals out of the range of integers can't be operated bitwise
+        assert!(engine.parse("let x = 5; return x ^ 100000000000000000000000000000000000000000.0;", ContextBuilder::new()).is_err());
+        for script in ["value && 1", "value || 1", "value ^ 1", "value << 1", "1 >> value"] {
+            assert!(matches!(execute(&engine, script, MoonValue::from(1e41)), Err(RuntimeError::FunctionError { .. })), "{script}");
+        }
+        assert!(matches!(execute(&engine, "value ^ 1", MoonValue::from(f64::NAN)), Err(RuntimeError::FunctionError { .. })));
+
+        assert_eq!(Ok(255), u8::try_from(MoonValue::from(255.9)));
+        assert_eq!(Err(ConversionError::new(MoonValueKind::Integer, MoonValue::from(256))), u8::try_from(MoonValue::from(256)));
+        assert_eq!(Err(ConversionError::new(MoonValueKind::Integer, MoonValue::from(-1.0))), u32::try_from(MoonValue::from(-1.0)));
+        assert_eq!(Ok(i64::MIN), i64::try_from(MoonValue::from(i64::MIN as f64)));
+        assert!(i64::try_from(MoonValue::from(-(i64::MIN as f64))).is_err());
+        assert!(f32::try_from(MoonValue::from(1e300)).is_err());
+
+        let mut engine = Engine::new();
+        engine.add_function(FunctionDefinition::new("heal", |amount: u8| amount));
+        assert!(matches!(execute(&engine, "heal(value)", MoonValue::from(300)), Err(RuntimeError::CannotParseArgument { .. })));
+    }
+
+    #[test]
+    fn test_engine_introspection() {
+        let mut engine = Engine::new().with_std_random();
+        engine.add_function(FunctionDefinition::new("get_hp", |hp: u16| hp).associated_type_name("Player").module_name("rpg"));
+        engine.add_function(FunctionDefinition::new("set_hp", |hp: u16, new_hp: u16| MoonValue::from([new_hp, hp]))
+            .associated_type_name("Player").module_name("rpg").mutating().known_return_type_name("int"));
+        engine.add_constant("MAX_HP", Constant::new(100).associated_type("Health"));
+
+        let functions = engine.functions();
+        let player_functions = functions.iter()
+            .filter(|function| function.associated_type_name == Some("Player"))
+            .map(|function| (function.name, function.module_name, function.number_of_params, function.inline, function.mutating, function.return_type_name))
+            .collect::<Vec<_>>();
+        assert_eq!(vec![
+            ("get_hp", Some("rpg"), 1, false, false, Some("int")),
+            ("set_hp", Some("rpg"), 2, false, true, Some("int")),
+        ], player_functions);
+        let random_int = functions.iter().find(|function| function.module_name == Some("random") && function.name == "int").unwrap();
+        assert_eq!(2, random_int.number_of_params);
+        assert!(functions.iter().any(|function| function.associated_type_name == Some("string") && function.name == "len"));
+
+        assert_eq!(vec![("MAX_HP", Some("Health"))], engine.constants().into_iter()
+            .map(|(name, constant)| (name, constant.type_name())).collect::<Vec<_>>());
+        assert_eq!(MoonValue::from(100), *engine.constant("MAX_HP").unwrap().value());
+        assert!(engine.binary_operators().contains(&"not in"));
+        assert_eq!(vec!["!", "-"], engine.unary_operators());
+        let type_names = engine.type_names();
+        assert!(["Health", "Player", "int", "string"].iter().all(|type_name| type_names.contains(type_name)));
+    }
+
+    #[test]
+    fn test_generate_docs() {
+        let mut engine = Engine::new();
+        engine.add_function(FunctionDefinition::new("get_hp", |hp: u16| hp).associated_type_name("Player"));
+        engine.add_function(FunctionDefinition::new("set_hp", |hp: u16, new_hp: u16| MoonValue::from([new_hp, hp]))
+            .associated_type_name("Player").mutating());
+        engine.add_function(FunctionDefinition::new("heal", |hp: u16, amount: u16| hp + amount)
+            .associated_type_name("Player")
+            .param_names(["player", "amount"])
+            .doc("Heals the player by <amount>."));
+        engine.add_function(FunctionDefinition::new("roll", |sides: u8| sides).module_name("dice"));
+        engine.add_constant("GREETING", "Hi & bye");
+
+        let markdown = engine.generate_docs(DocsFormat::Markdown);
+        for expected in [
+            "## Module dice\n\n### `dice/roll(arg1) -> int`\n\n",
+            "## Type Player\n\n### Properties\n\n- `hp: int` (read and write)\n\n### Methods\n\n#### `player.heal(amount) -> int`\n\nHeals the player by <amount>.\n\n",
+            "## Constants\n\n- `GREETING: string = \"Hi & bye\"`\n",
+        ] {
+            assert!(markdown.contains(expected), "{expected} not found in:\n{markdown}");
+        }
+        assert!(!markdown.contains("get_hp"));
+
+        let html = engine.generate_docs(DocsFormat::Html);
+        for expected in [
+            "<h4><code>player.heal(amount) -&gt; int</code></h4>\n<p>Heals the player by &lt;amount&gt;.</p>\n",
+            "<li><code>GREETING: string = &quot;Hi &amp; bye&quot;</code></li>",
+        ] {
+            assert!(html.contains(expected), "{expected} not found in:\n{html}");
+        }
+    }
+
+    #[test]
+    fn test_remove_functions_and_constants() {
+        let mut engine = Engine::new();
+        engine.add_function(FunctionDefinition::new("get_hp", |hp: u16| hp)
+            .associated_type_name("Player").module_name("combat"));
+        engine.add_function(FunctionDefinition::new("roll", || 4).module_name("dice"));
+        engine.add_constant("MAX_HP", 100);
+
+        let parses = |engine: &Engine, script| engine.parse(script, ContextBuilder::new()
+            .with_variable(InputVariable::new("player").associated_type("Player").lazy_value(|| 7)))
+            .is_ok();
+        assert!(parses(&engine, "let name = \"abc\"; return name.len();"));
+        assert!(!engine.remove_function(Some("string"), None, "len").is_empty());
+        assert!(!parses(&engine, "let name = \"abc\"; return name.len();"));
+        assert!(parses(&engine, "let items = [1]; return items.len();"));
+
+        assert!(parses(&engine, "return player.hp;"));
+        assert!(engine.remove_module("combat"));
+        assert!(!parses(&engine, "return player.hp;"));
+        assert!(!engine.type_names().contains(&"Player"));
+
+        engine.add_function(FunctionDefinition::new("roll", || 6).module_name("dice"));
+        assert_eq!(MoonValue::from(6), engine.parse("return dice/roll();", ContextBuilder::new()).unwrap().execute().unwrap());
+        assert!(!engine.remove_function(None, Some("dice"), "roll").is_empty());
+        assert!(engine.functions().iter().all(|function| function.module_name != Some("dice")));
+
+        assert!(engine.remove_constant("MAX_HP").is_some());
+        assert!(engine.remove_constant("MAX_HP").is_none());
+        assert!(engine.constants().is_empty());
+
+        let empty = Engine::empty();
+        assert!(empty.functions().is_empty());
+        assert_eq!(Engine::new().binary_operators(), empty.binary_operators());
+        assert!(!parses(&empty, "let name = \"abc\"; return name.len();"));
+    }
+
+    #[test]
+    fn test_child_engine() {
+        let mut parent = Engine::new();
+        parent.add_function(FunctionDefinition::new("greet", || "Hello").module_name("ui"));
+        parent.add_function(FunctionDefinition::new("double", |n: i32| n * 2));
+        parent.add_constant("MAX_HP", 100);
+        let parent = Arc::new(parent);
+
+        let mut child = Engine::child(&parent);
+        child.add_function(FunctionDefinition::new("greet", || "Hi").module_name("ui"));
+        child.add_constant("LEVEL", 3);
+        let execute = |engine: &Engine, script| engine.parse(script, ContextBuilder::new()).unwrap().execute().unwrap();
+
+        assert_eq!(MoonValue::from("Hi"), execute(&child, "return ui/greet();"));
+        assert_eq!(MoonValue::from("Hello"), execute(&parent, "return ui/greet();"));
+        assert_eq!(MoonValue::from(206), execute(&child, "return double(MAX_HP) + 2 * LEVEL;"));
+        assert_eq!(MoonValue::from(3), execute(&child, "let name = \"abc\"; return name.len();"));
+        assert!(parent.parse("return LEVEL;", ContextBuilder::new()).is_err());
+
+        assert_eq!(1, child.functions().iter().filter(|function| function.name == "greet").count());
+        assert_eq!(parent.functions().len(), child.functions().len());
+        assert_eq!(vec!["LEVEL", "MAX_HP"], child.constants().into_iter().map(|(name, _)| name).collect::<Vec<_>>());
+        assert_eq!(parent.binary_operators(), child.binary_operators());
+
+        // Removing from the child hides what the parent has too, but only for the child
+        assert!(!child.remove_function(None, Some("ui"), "greet").is_empty());
+        assert!(child.parse("return ui/greet();", ContextBuilder::new()).is_err());
+        assert_eq!(MoonValue::from("Hello"), execute(&parent, "return ui/greet();"));
+        child.add_function(FunctionDefinition::new("greet", || "Hey").module_name("ui"));
+        assert_eq!(MoonValue::from("Hey"), execute(&child, "return ui/greet();"));
+        let mut sandbox = Engine::child(&parent);
+        assert_eq!(1, sandbox.remove_function(None, None, "double").len());
+        assert!(sandbox.remove_function(None, None, "double").is_empty());
+        assert!(sandbox.parse("return double(2);", ContextBuilder::new()).is_err());
+        assert!(sandbox.remove_module("ui"));
+        assert!(!sandbox.remove_module("ui"));
+        assert!(sandbox.parse("return ui/greet();", ContextBuilder::new()).is_err());
+        assert_eq!(Some(MoonValue::from(100)), sandbox.remove_constant("MAX_HP").map(|constant| constant.value().clone()));
+        assert!(sandbox.parse("return MAX_HP;", ContextBuilder::new()).is_err());
+        assert!(sandbox.functions().iter().all(|function| function.name != "double" && function.module_name != Some("ui")));
+        assert!(sandbox.constants().is_empty());
+        #[cfg(feature = "std")]
+        {
+            sandbox.remove_function(None, None, "print");
+            sandbox.remove_function(None, None, "println");
+            assert!(sandbox.parse("println(\"hi\");", ContextBuilder::new()).is_err());
+            assert!(parent.parse("println(\"hi\");", ContextBuilder::new()).is_ok());
+        }
+
+        let child = Arc::new(child.with_overflow_policy(OverflowPolicy::Saturating));
+        let grandchild = Engine::child(&child);
+        assert_eq!(MoonValue::from(i128::MAX), execute(&grandchild, "return 170141183460469231731687303715884105727 + MAX_HP;"));
+        assert!(parent.parse("return 170141183460469231731687303715884105727 + MAX_HP;", ContextBuilder::new()).is_err());
+        assert_eq!(MoonValue::from(3), execute(&grandchild, "return LEVEL;"));
+    }
+
+    #[test]
+    fn test_function_resolution_order() {
+        let mut engine = Engine::new();
+        for (module, value) in [("dice", 4), ("loaded_dice", 6), ("cards", 1)] {
+            engine.add_function(FunctionDefinition::new("roll", move || value).module_name(module));
+            engine.add_function(FunctionDefinition::new("shuffle", move |_: i32| value)
+                .associated_type_of::<i32>().module_name(module));
+        }
+        for module in ["a", "b", "c", "d", "e", "f", "g", "h"] {
+            engine.add_function(FunctionDefinition::new(format!("only_in_{module}"), || 0).module_name(module));
+        }
+        engine.add_function(FunctionDefinition::new("only_in_h", || 1));
+        let execute = |engine: &Engine, script: &str, context: ContextBuilder| -> Result<MoonValue, String> {
+            engine.parse(script, context)
+                .map(|ast| ast.execute().unwrap())
+                .map_err(|error| format!("{error}"))
+        };
+
+        for module in ["a", "b", "c", "d", "e", "f", "g"] {
+            assert_eq!(Ok(MoonValue::from(0)), execute(&engine, &format!("return only_in_{module}();"), ContextBuilder::new()));
+        }
+        assert_eq!(Ok(MoonValue::from(1)), execute(&engine, "use h; return only_in_h();", ContextBuilder::new()));
+
+        let error = execute(&engine, "return roll();", ContextBuilder::new()).unwrap_err();
+        assert!(error.contains("The function roll exists in many modules: cards, dice, loaded_dice."), "{error}");
+        let error = execute(&engine, "let n = 1; return n.shuffle();", ContextBuilder::new()).unwrap_err();
+        assert!(error.contains("The function shuffle for type int exists in many modules"), "{error}");
+
+        assert_eq!(Ok(MoonValue::from(6)), execute(&engine, "use loaded_dice;\nuse dice;\nreturn roll();", ContextBuilder::new()));
+        assert_eq!(Ok(MoonValue::from(4)), execute(&engine, "use dice; let n = 1; return n.shuffle();", ContextBuilder::new()));
+        assert_eq!(Ok(MoonValue::from(1)), execute(&engine, "return roll();", ContextBuilder::new().with_used_module("cards")));
+        assert_eq!(Ok(MoonValue::from(4)), execute(&engine, "use dice; return roll();", ContextBuilder::new().with_used_module("cards")));
+        assert_eq!(Ok(MoonValue::from(6)), execute(&engine, "use dice; return loaded_dice/roll();", ContextBuilder::new()));
+        let error = execute(&engine, "use tarot; return roll();", ContextBuilder::new()).unwrap_err();
+        assert!(error.contains("There is no module named tarot."), "{error}");
+
+        let engine = Arc::new(engine.with_prelude_module("cards"));
+        assert_eq!(Ok(MoonValue::from(1)), execute(&engine, "return roll();", ContextBuilder::new()));
+        assert_eq!(Ok(MoonValue::from(6)), execute(&engine, "use loaded_dice; return roll();", ContextBuilder::new()));
+        let child = Engine::child(&engine).with_prelude_module("dice");
+        assert_eq!(Ok(MoonValue::from(4)), execute(&child, "return roll();", ContextBuilder::new()));
+        assert_eq!(vec!["dice", "cards"], child.prelude_modules());
+    }
+
+    #[test]
+    fn test_operator_overloading() {
+        let mut engine = Engine::new();
+        engine.add_function(FunctionDefinition::new("read", |value: MoonValue| value));
+        engine.add_binary_operator(FunctionDefinition::new("^", |base: i64, exponent: u32| base.pow(exponent)).inline()).unwrap();
+        engine.add_binary_operator(FunctionDefinition::new("+", |lhs: Vec<f64>, rhs: Vec<f64>| [lhs[0] + rhs[0], lhs[1] + rhs[1]])
+            .associated_type_name("vec2").known_return_type_name("vec2").inline()).unwrap();
+        engine.add_binary_operator(FunctionDefinition::new("*", |vector: Vec<f64>, scale: f64| [vector[0] * scale, vector[1] * scale])
+            .associated_type_name("vec2")).unwrap();
+        engine.add_binary_operator(FunctionDefinition::new("+", |lhs: Vec<MoonValue>, rhs: Vec<MoonValue>| {
+            lhs.into_iter().chain(rhs).collect::<Vec<_>>()
+        }).associated_type_name("array")).unwrap();
+        engine.add_unary_operator(FunctionDefinition::new("-", |vector: Vec<f64>| [-vector[0], -vector[1]])
+            .associated_type_name("vec2")).unwrap();
+        let context = || ContextBuilder::new()
+            .with_variable(InputVariable::new("position").associated_type("vec2").value(vec![1.0, 2.0]))
+            .with_variable(InputVariable::new("speed").associated_type("vec2").value(vec![0.5, 0.5]));
+        let execute = |engine: &Engine, script: &str| -> MoonValue {
+            engine.parse(script, context()).unwrap().execute().unwrap()
+        };
+        let decimals = |values: Vec<f64>| MoonValue::from(values);
+
+        assert_eq!(MoonValue::from(8), execute(&engine, "return 2 ^ 3;"));
+        // When running the script the array overload would be tried first, concatenating them
+        assert_eq!(decimals(vec![2.0, 3.0]), execute(&engine, "return position + speed + speed;"));
+        assert_eq!(decimals(vec![-1.0, -2.0]), execute(&engine, "return -position;"));
+        assert_eq!(MoonValue::from(-3), execute(&engine, "return -3;"));
+
+        // Values don't carry custom types when running, so the vec2 overloads are not used then
+        let mut vectors = Engine::new();
+        vectors.add_function(FunctionDefinition::new("read", |value: MoonValue| value));
+        vectors.add_binary_operator(FunctionDefinition::new("+", |lhs: Vec<f64>, rhs: Vec<f64>| [lhs[0] + rhs[0], lhs[1] + rhs[1]])
+            .associated_type_name("vec2")).unwrap();
+        assert_eq!(decimals(vec![1.0, 2.0, 3.0, 4.0]), execute(&vectors, "return read(position) + [3.0, 4.0];"));
+        assert_eq!(MoonValue::from(6), execute(&engine, "return read(2) * read(3);"));
+        assert_eq!(MoonValue::from(vec![1, 2, 3]), execute(&engine, "return read([1, 2]) + read([3]);"));
+        assert_eq!(MoonValue::from("ab"), execute(&engine, "return read(\"a\") + read(\"b\");"));
+
+        let engine = Arc::new(engine);
+        let mut child = Engine::child(&engine);
+        child.add_binary_operator(FunctionDefinition::new("+", |lhs: Vec<f64>, rhs: Vec<f64>| [lhs[0] - rhs[0], lhs[1] - rhs[1]])
+            .associated_type_name("vec2")).unwrap();
+        assert_eq!(decimals(vec![0.5, 1.5]), execute(&child, "return position + speed;"));
+        assert_eq!(decimals(vec![1.5, 2.5]), execute(&engine, "return position + speed;"));
+        assert_eq!(decimals(vec![2.0, 4.0]), execute(&child, "return position * 2.0;"));
+        assert_eq!(MoonValue::from(vec![1, 3]), execute(&child, "return read([1]) + read([3]);"));
+
+        let mut engine = Engine::new();
+        assert_eq!(Err(OperatorError::UnknownOperator { operator: "**".to_string() }),
+                   engine.add_binary_operator(FunctionDefinition::new("**", |lhs: i64, rhs: i64| lhs * rhs)));
+        assert_eq!(Err(OperatorError::UnknownOperator { operator: "!=".to_string() }),
+                   engine.add_unary_operator(FunctionDefinition::new("!=", |value: bool| !value)));
+        assert_eq!(Err(OperatorError::WrongNumberOfParams { operator: "+".to_string(), expected: 2, found: 1 }),
+                   engine.add_binary_operator(FunctionDefinition::new("+", |value: i64| value)));
+        assert_eq!(Err(OperatorError::WrongNumberOfParams { operator: "-".to_string(), expected: 1, found: 2 }),
+                   engine.add_unary_operator(FunctionDefinition::new("-", |lhs: i64, rhs: i64| lhs - rhs).associated_type_name("vec2")));
+        assert_eq!(MoonValue::from(3), execute(&engine, "return 1 + 2;"));
+        assert!(engine.unary_operator_overloads("-").is_empty());
+
+        engine.add_binary_operator(FunctionDefinition::new("+", |lhs: i64, rhs: i64| lhs - rhs)).unwrap();
+        let engine = Arc::new(engine.with_overflow_policy(OverflowPolicy::Wrapping));
+        assert_eq!(MoonValue::from(-1), execute(&engine, "return 1 + 2;"));
+        assert_eq!(MoonValue::from(-2), execute(&engine, "return 170141183460469231731687303715884105727 * 2;"));
+        let child = Engine::child(&engine).with_overflow_policy(OverflowPolicy::Saturating);
+        assert_eq!(MoonValue::from(-1), execute(&child, "return 1 + 2;"));
+        assert_eq!(MoonValue::from(i128::MAX), execute(&child, "return 170141183460469231731687303715884105727 * 2;"));
+    }
+
+    #[test]
+    fn test_function_overloading() {
+        let mut engine = Engine::new();
+        engine.add_function(FunctionDefinition::new("read", |value: MoonValue| value));
+        engine.add_function(FunctionDefinition::new("spawn", |x: i64, y: i64| format!("{x},{y}")));
+        engine.add_function(FunctionDefinition::new("spawn", |x: i64, y: i64, z: i64| format!("{x},{y},{z}")));
+        engine.add_function(FunctionDefinition::new("describe", |value: i64| format!("number {value}")).inline());
+        engine.add_function(FunctionDefinition::new("describe", |value: String| format!("text {value}")).inline());
+        engine.add_function(FunctionDefinition::new("describe", |value: bool| format!("flag {value}")));
+        engine.add_function(FunctionDefinition::new("pick", |_value: i64| "int"));
+        engine.add_function(FunctionDefinition::new("pick", |_value: f64| "decimal"));
+        let execute = |engine: &Engine, script: &str| -> MoonValue {
+            engine.parse(script, ContextBuilder::new()).unwrap().execute().unwrap()
+        };
+
+        assert_eq!(MoonValue::from("1,2"), execute(&engine, "return spawn(1, 2);"));
+        assert_eq!(MoonValue::from("1,2,3"), execute(&engine, "return spawn(1, 2, 3);"));
+        assert!(engine.parse("return spawn(1);", ContextBuilder::new()).is_err());
+
+        assert_eq!(MoonValue::from("number 5"), execute(&engine, "return describe(5);"));
+        assert_eq!(MoonValue::from("text five"), execute(&engine, "return describe(\"five\");"));
+        assert_eq!(MoonValue::from("flag true"), execute(&engine, "return describe(read(yes));"));
+        assert_eq!(MoonValue::from("number 5"), execute(&engine, "return describe(read(5));"));
+        assert!(engine.parse("return describe(null);", ContextBuilder::new()).is_err());
+
+        assert_eq!(MoonValue::from("decimal"), execute(&engine, "return pick(2.5);"));
+        // Integers can be given where decimals are expected, so both overloads receive it
+        assert!(engine.parse("return pick(2);", ContextBuilder::new()).is_err());
+        assert!(engine.parse("return pick(2);", ContextBuilder::new().with_coercion_policy(CoercionPolicy::Strict)).is_ok());
+
+        engine.add_function(FunctionDefinition::new("area", |side: f64| side * side));
+        engine.add_function(FunctionDefinition::new("area", |width: f64, height: f64| width * height));
+        // Only the overload receiving as many arguments is considered, so the argument is coerced
+        assert_eq!(MoonValue::from(4.0), execute(&engine, "return area(\"2\");"));
+        assert_eq!(MoonValue::from(6.0), execute(&engine, "return area(2, 3.0);"));
+
+        engine.add_function(FunctionDefinition::new("describe", |value: i64| format!("int {value}")));
+        assert_eq!(MoonValue::from("int 5"), execute(&engine, "return describe(5);"));
+        assert_eq!(3, engine.functions().into_iter().filter(|function| function.name == "describe").count());
+
+        let engine = Arc::new(engine);
+        let mut child = Engine::child(&engine);
+        child.add_function(FunctionDefinition::new("spawn", |x: i64| format!("{x}")));
+        assert_eq!(MoonValue::from("1"), execute(&child, "return spawn(1);"));
+        assert_eq!(MoonValue::from("1,2"), execute(&child, "return spawn(1, 2);"));
+        // The overloads of the parent are hidden too, so they are returned along with the own one
+        assert_eq!(3, child.remove_function(None, None, "spawn").len());
+        assert!(child.parse("return spawn(1);", ContextBuilder::new()).is_err());
+        assert!(child.parse("return spawn(1, 2);", ContextBuilder::new()).is_err());
+        assert_eq!(MoonValue::from("1,2"), execute(&engine, "return spawn(1, 2);"));
+    }
+
+    #[test]
+    fn test_host_state() {
+        struct World {
+            hp: i64,
+            log: Vec<String>,
+        }
+        let mut engine = Engine::new();
+        engine.add_function(FunctionDefinition::new("damage", |world: &mut World, amount: i64| {
+            world.hp -= amount;
+            world.hp
+        }));
+        engine.add_function(FunctionDefinition::new("hp", |world: &World| world.hp));
+        engine.add_function(FunctionDefinition::new("log", |world: &mut World, message: String| -> Result<(), String> {
+            if message.is_empty() {
+                return Err("Empty message".to_string());
+            }
+            world.log.push(message);
+            Ok(())
+        }));
+        let ast = engine.parse(r#"
+            damage(30);
+            damage(hp() - 50);
+            log("Ouch");
+            return hp();
+        "#, ContextBuilder::new()).unwrap();
+
+        let mut world = World { hp: 100, log: Vec::new() };
+        assert_eq!(MoonValue::from(50), ast.executor().execute_with_state(&mut world).unwrap());
+        assert_eq!((50, vec!["Ouch".to_string()]), (world.hp, world.log));
+
+        let optimized_ast = ast.clone().to_optimized_ast();
+        let mut world = World { hp: 200, log: Vec::new() };
+        let executor = optimized_ast.executor();
+        assert_eq!(MoonValue::from(50), executor.clone().execute_with_state(&mut world).unwrap());
+        assert_eq!(50, world.hp);
+        // Executors can be reused for many states
+        let mut world = World { hp: 80, log: Vec::new() };
+        assert_eq!(MoonValue::from(50), executor.execute_with_state(&mut world).unwrap());
+
+        assert!(matches!(ast.execute(), Err(RuntimeError::StateNotGiven { expected_type, .. }) if expected_type.ends_with("World")));
+        assert!(matches!(ast.executor().execute_with_state(&mut 5), Err(RuntimeError::StateNotGiven { .. })));
+        let mut world = World { hp: 100, log: Vec::new() };
+        assert!(matches!(engine.parse("log(\"\");", ContextBuilder::new()).unwrap().executor().execute_with_state(&mut world),
+            Err(RuntimeError::FunctionError { .. })));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn test_json() {
+        let value = MoonValue::Map(vec![
+            (MoonValue::from("name"), MoonValue::from("Moon \"the\" cat\n")),
+            (MoonValue::from(1), MoonValue::from([MoonValue::Null, MoonValue::from(true)])),
+            (MoonValue::from("big"), MoonValue::from(i128::MAX)),
+            (MoonValue::from("decimals"), MoonValue::from([3.0, 0.1, 1e300, -0.0])),
+        ]);
+        let json = value.to_json().unwrap();
+        assert_eq!(r#"{"name":"Moon \"the\" cat\n","1":[null,true],"big":170141183460469231731687303715884105727,"decimals":[3.0,0.1,1e300,-0.0]}"#, json);
+        let decoded = MoonValue::from_json(&json).unwrap();
+        assert_eq!(MoonValue::Map(vec![
+            (MoonValue::from("name"), MoonValue::from("Moon \"the\" cat\n")),
+            (MoonValue::from("1"), MoonValue::from([MoonValue::Null, MoonValue::from(true)])),
+            (MoonValue::from("big"), MoonValue::from(i128::MAX)),
+            (MoonValue::from("decimals"), MoonValue::from([3.0, 0.1, 1e300, -0.0])),
+        ]), decoded);
+
+        assert_eq!(MoonValue::Decimal(1e39), MoonValue::from_json("1000000000000000000000000000000000000000").unwrap());
+        assert_eq!(MoonValue::from("é😀\u{1}"), MoonValue::from_json(r#" "\u00e9\ud83d\ude00\u0001" "#).unwrap());
+        assert_eq!(r#""\u0001""#, MoonValue::from("\u{1}").to_json().unwrap());
+        assert!(MoonValue::Decimal(f64::NAN).to_json().is_err());
+        assert!(MoonValue::from_json("[1, 2").is_err());
+        assert!(MoonValue::from_json("01").is_err());
+        assert!(MoonValue::from_json("{1: 2}").is_err());
+        let nested = |depth: usize| "[".repeat(depth) + &"]".repeat(depth);
+        assert!(MoonValue::from_json(&nested(crate::MAX_JSON_DEPTH)).is_ok());
+        assert_eq!(Err(crate::JsonError::TooDeeplyNested { position: crate::MAX_JSON_DEPTH }), MoonValue::from_json(&nested(crate::MAX_JSON_DEPTH + 1)));
+        assert!(MoonValue::from_json(&"{\"a\":".repeat(100_000)).is_err());
+
+        let engine = Engine::new().with_std_json();
+        let ast = engine.parse(r#"
+            let stats = json/decode(text);
+            [stats, json/encode([1, 2.5, "moon"])]
+        "#, ContextBuilder::new().with_variable(InputVariable::new("text"))).unwrap();
+        let expected = MoonValue::from([
+            MoonValue::Map(vec![(MoonValue::from("hp"), MoonValue::from(7))]),
+            MoonValue::from(r#"[1,2.5,"moon"]"#),
+        ]);
+        assert_eq!(expected, ast.executor().push_variable("text", r#"{"hp": 7}"#).execute().unwrap());
+        assert!(ast.executor().push_variable("text", "[".repeat(100_000)).execute().is_err());
+        assert_eq!(expected, ast.to_optimized_ast().executor().push_variable("text", r#"{"hp": 7}"#).execute().unwrap());
+        assert!(engine.parse("json/decode(\"[1,\")", ContextBuilder::new()).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde() {
+        use crate::{from_value, to_value};
+
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        enum Difficulty {
+            Easy,
+            Custom(u8),
+            Mixed { enemies: u8, bosses: u8 },
+        }
+
+        #[derive(serde::Serialize, serde::Deserialize, Debug, PartialEq)]
+        struct Settings {
+            volume: u8,
+            name: String,
+            scale: Option<f64>,
+            resolution: (u32, u32),
+            difficulties: Vec<Difficulty>,
+        }
+
+        let result = Engine::new().parse(r#"
+            let width = 1920;
+            {
+                "volume": 80,
+                "name": "moon",
+                "scale": null,
+                "resolution": [width, width / 16 * 9],
+                "difficulties": ["Easy", {"Custom": 7}, {"Mixed": {"enemies": 3, "bosses": 1}}]
+            }
+        "#, ContextBuilder::new()).unwrap().execute().unwrap();
+        let settings: Settings = from_value(result.clone()).unwrap();
+        let expected = Settings {
+            volume: 80,
+            name: "moon".to_string(),
+            scale: None,
+            resolution: (1920, 1080),
+            difficulties: vec![Difficulty::Easy, Difficulty::Custom(7), Difficulty::Mixed { enemies: 3, bosses: 1 }],
+        };
+        assert_eq!(expected, settings);
+        assert_eq!(result, to_value(&expected).unwrap());
+
+        assert_eq!(MoonValue::Integer(i128::MAX), from_value::<MoonValue>(to_value(&i128::MAX).unwrap()).unwrap());
+        assert!(from_value::<u8>(MoonValue::Integer(300)).is_err());
+        assert!(from_value::<Settings>(MoonValue::from([1, 2])).is_err());
+        assert!(to_value(&u128::MAX).is_err());
+    }
+
+    #[cfg(feature = "derive")]
+    #[test]
+    fn test_derive_moon_type() {
+        use crate::MoonType;
+
+        #[derive(MoonType, Clone, Debug, PartialEq)]
+        struct Position(i32, i32);
+
+        #[derive(MoonType, Clone, Debug, PartialEq)]
+        struct Marker;
+
+        #[derive(MoonType, Clone, Debug, PartialEq)]
+        struct Player {
+            pub name: String,
+            pub hp: u16,
+            pub r#type: String,
+            level: u8,
+        }
+
+        let player = Player { name: "Moon".to_string(), hp: 20, r#type: "mage".to_string(), level: 3 };
+        assert_eq!(MoonValue::Map(vec![
+            (MoonValue::from("name"), MoonValue::from("Moon")),
+            (MoonValue::from("hp"), MoonValue::from(20)),
+            (MoonValue::from("type"), MoonValue::from("mage")),
+            (MoonValue::from("level"), MoonValue::from(3)),
+        ]), MoonValue::from(player.clone()));
+        assert_eq!(Ok(player.clone()), Player::try_from(MoonValue::from(player.clone())));
+        assert_eq!(Ok(Position(3, -4)), Position::try_from(MoonValue::from(Position(3, -4))));
+        assert_eq!(MoonValue::from([3, -4]), MoonValue::from(Position(3, -4)));
+        assert_eq!(Ok(Marker), Marker::try_from(MoonValue::from(Marker)));
+        assert!(Player::try_from(MoonValue::from([1, 2])).is_err());
+        assert!(Position::try_from(MoonValue::from([1, 2, 3])).is_err());
+        let wrong_player = MoonValue::Map(vec![(MoonValue::from("name"), MoonValue::from(5))]);
+        assert_eq!(".name", Player::try_from(wrong_player).unwrap_err().path);
+
+        let mut engine = Engine::new();
+        Player::register(&mut engine);
+        let context = ContextBuilder::new()
+            .with_variable(InputVariable::new("player").associated_type_of::<Player>());
+        let ast = engine.parse(r#"
+            player.hp = player.hp - 5;
+            player.name = player.name + " " + player.type;
+            player
+        "#, context).unwrap();
+        let expected = Player { name: "Moon mage".to_string(), hp: 15, ..player.clone() };
+        let result: Player = ast.executor().push_variable("player", player.clone()).execute().unwrap().try_into().unwrap();
+        assert_eq!(expected, result);
+        let result: Player = ast.to_optimized_ast().executor().push_variable("player", player).execute().unwrap().try_into().unwrap();
+        assert_eq!(expected, result);
+
+        let context = ContextBuilder::new()
+            .with_variable(InputVariable::new("player").associated_type_of::<Player>());
+        assert!(engine.parse("player.level", context).is_err());
+    }
+
     #[cfg_attr(not(feature = "std"), test)]
     fn test_custom_unnamed_type() {
         let _ = simple_logger::init_with_level(log::Level::Trace);
@@ -247,7 +1223,7 @@ mod test {
 
 #[cfg(test)]
 mod book_tests {
-    use crate::{ContextBuilder, Engine, FunctionDefinition, InputVariable, MoonValue};
+    use crate::{ContextBuilder, ConversionError, Engine, FunctionDefinition, InputVariable, MoonValue, MoonValueKind};
     use alloc::format;
     use alloc::string::{String, ToString};
 
@@ -1,15 +1,15 @@
 use alloc::fmt::{Debug, Display, Formatter};
 use alloc::format;
-use alloc::string::String;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
 
 use pest::error::LineColLocation;
 use simple_detailed_error::{SimpleError, SimpleErrorDetail, SimpleErrorExplanation};
 
 use crate::execution::RuntimeError;
 use crate::parsing::Rule;
+use crate::value::MoonValue;
 
-#[cfg(feature = "colorization")]
-use alloc::vec::Vec;
 #[cfg(feature = "colorization")]
 use colored::Colorize;
 #[cfg(feature = "colorization")]
@@ -95,12 +95,30 @@ pub enum ASTBuildingError<'input> {
         /// Predicate where it happens (This is a reference to the script that is tried to compile).
         predicate: &'input str
     },
+    /// The predicate of an 'assert' statement is known at compile time to be false
+    AssertionAlwaysFails {
+        /// Predicate of the assertion (This is a reference to the script that is tried to compile).
+        predicate: &'input str,
+        /// Message of the assertion if it was known at compile time.
+        message: Option<String>,
+    },
     /// An ident of a variable was found, but the name doesn't match to a variable that was created
     /// inside the script, nor an Engine's constants or the ContextBuilder input variables
     VariableNotInScope {
         /// Name of the variable.
         variable_name: &'input str
     },
+    /// A keyword of the language was used where a variable name was expected
+    ReservedKeyword {
+        /// The keyword (This is a reference to the script that is tried to compile).
+        keyword: &'input str
+    },
+    /// An input variable of the ContextBuilder has a name that isn't a valid identifier, or it's a
+    /// keyword of the language
+    InvalidVariableName {
+        /// Name of the variable.
+        variable_name: String
+    },
     /// Used an operator that doesn't exist, this will likely never happen
     OperatorNotFound {
         /// Name of the operator as symbol.
@@ -115,6 +133,40 @@ pub enum ASTBuildingError<'input> {
         /// Module (Might be none if it's not specified in the script).
         module: Option<&'input str>,
     },
+    /// A function was called without a module, but many modules have a function with that name
+    /// and none of them is used by the script nor is in the prelude of the Engine
+    AmbiguousFunction {
+        /// Name of the function.
+        function_name: String,
+        /// Associated type (Might be none if it's not specified in the script).
+        associated_to_type: Option<String>,
+        /// Modules having a function with that name.
+        modules: Vec<String>,
+    },
+    /// A function has many overloads that can receive the arguments it's called with, and their
+    /// types are known, so choosing one when running the script wouldn't help
+    AmbiguousOverload {
+        /// Name of the function.
+        function_name: String,
+        /// Types of the arguments, if they are known.
+        argument_types: Vec<Option<String>>,
+        /// Parameters of the overloads that can receive the arguments, like `(int, any)`.
+        overloads: Vec<String>,
+    },
+    /// A function has many overloads, but none of them can receive the arguments it's called with
+    NoMatchingOverload {
+        /// Name of the function.
+        function_name: String,
+        /// Types of the arguments, if they are known.
+        argument_types: Vec<Option<String>>,
+        /// Parameters of every overload of the function, like `(int, any)`.
+        overloads: Vec<String>,
+    },
+    /// A script used a module with `use module;` but the Engine doesn't have any function on it
+    ModuleNotFound {
+        /// Name of the module (This is a reference to the script that is tried to compile).
+        module: &'input str
+    },
     /// A property was specified, but it doesn't exist on the Engine (See the Properties section of
     /// the book for more information)
     PropertyFunctionNotFound {
@@ -171,6 +223,21 @@ pub enum ASTBuildingError<'input> {
         /// Maximum bound the string should have been
         upper_bound: i128,
     },
+    /// A destructuring assignment like 'let [x, y] = value;' received a constant value whose shape
+    /// doesn't match the pattern.
+    CannotDestructure {
+        /// Pattern of the assignment (This is a reference to the script that is tried to compile).
+        pattern: &'input str,
+        /// Value that was tried to be destructured.
+        value: MoonValue,
+    },
+    /// A destructuring assignment like 'let [x, x] = value;' binds the same name more than once.
+    RepeatedDestructuringName {
+        /// Pattern of the assignment (This is a reference to the script that is tried to compile).
+        pattern: &'input str,
+        /// Name bound more than once.
+        name: &'input str,
+    },
     /// A decimal value could not be parsed in range
     CannotParseDecimal {
         /// Value (This is a reference to the script that is tried to compile).
@@ -182,6 +249,13 @@ pub enum ASTBuildingError<'input> {
     },
 }
 
+/// Describes the types of some arguments, like `(int, unknown)`.
+fn describe_argument_types(argument_types: &[Option<String>]) -> String {
+    format!("({})", argument_types.iter()
+        .map(|argument_type| argument_type.as_deref().unwrap_or("unknown"))
+        .collect::<Vec<_>>().join(", "))
+}
+
 #[cfg(not(feature = "colorization"))]
 trait PseudoColored {
     fn green(&self) -> &Self { self }
@@ -204,6 +278,22 @@ impl<'input> SimpleErrorDetail for ASTBuildingError<'input> {
                 #[cfg(feature = "colorization")]
                 colorization_markers.push((predicate, style::Clear + foreground::Red));
             }
+            ASTBuildingError::AssertionAlwaysFails { predicate, message } => {
+                explanation = match message {
+                    Some(message) => format!("The assertion '{}' always fails: {message}", predicate.bold()),
+                    None => format!("The assertion '{}' always fails", predicate.bold()),
+                };
+                #[cfg(feature = "colorization")]
+                colorization_markers.push((predicate, style::Clear + foreground::Red));
+            }
+            ASTBuildingError::ReservedKeyword { keyword } => {
+                explanation = format!("{} is a keyword, so it cannot be used as a variable name.", keyword.bold());
+                #[cfg(feature = "colorization")]
+                colorization_markers.push((keyword, style::Clear + foreground::Red));
+            }
+            ASTBuildingError::InvalidVariableName { variable_name } => {
+                explanation = format!("The input variable name '{}' is not a valid identifier or it's a keyword.", variable_name.bold());
+            }
             ASTBuildingError::VariableNotInScope { variable_name } => {
                 explanation = format!("The variable {} does not exist.", variable_name.bold());
                 solution = format!("If this is a local variable, create it before using it, like:\nlet {} = *{}*", variable_name.green().bold(), "your value".italic());
@@ -225,6 +315,35 @@ impl<'input> SimpleErrorDetail for ASTBuildingError<'input> {
                 #[cfg(feature = "colorization")]
                 colorization_markers.push((function_name, style::Clear + foreground::Red));
             }
+            ASTBuildingError::AmbiguousFunction { function_name, associated_to_type, modules } => {
+                explanation = format!("The function {}{} exists in many modules: {}.",
+                                      function_name.bold(),
+                                      associated_to_type.as_ref().map(|associated_type| format!(" for type {associated_type}")).unwrap_or_default(),
+                                      modules.join(", ")
+                );
+                solution = format!("Name the module when calling it, like {}, or use the module at the start of the script, like:\nuse {};",
+                                   format!("{}/{function_name}(...)", modules[0]).green().bold(), modules[0].green().bold());
+                #[cfg(feature = "colorization")]
+                colorization_markers.push((function_name, style::Clear + foreground::Red));
+            }
+            ASTBuildingError::AmbiguousOverload { function_name, argument_types, overloads } => {
+                explanation = format!("The call to {} with arguments of types {} matches many of its overloads: {}.",
+                                      function_name.bold(), describe_argument_types(argument_types), overloads.join(", "));
+                solution = "Give arguments whose types tell the overloads apart.".to_string();
+                #[cfg(feature = "colorization")]
+                colorization_markers.push((function_name, style::Clear + foreground::Red));
+            }
+            ASTBuildingError::NoMatchingOverload { function_name, argument_types, overloads } => {
+                explanation = format!("No overload of {} receives arguments of types {}, its overloads are: {}.",
+                                      function_name.bold(), describe_argument_types(argument_types), overloads.join(", "));
+                #[cfg(feature = "colorization")]
+                colorization_markers.push((function_name, style::Clear + foreground::Red));
+            }
+            ASTBuildingError::ModuleNotFound { module } => {
+                explanation = format!("There is no module named {}.", module.bold());
+                #[cfg(feature = "colorization")]
+                colorization_markers.push((module, style::Clear + foreground::Red));
+            }
             ASTBuildingError::PropertyFunctionNotFound { preferred_property_to_find, original_property, typename } => {
                 let typename = typename.as_ref().map(|v| &**v).unwrap_or("Unknown type");
                 explanation = format!("The type {typename} does not have a property named {} as there is no associated function named {preferred_property_to_find} nor {original_property}.",
@@ -263,6 +382,16 @@ impl<'input> SimpleErrorDetail for ASTBuildingError<'input> {
                 #[cfg(feature = "colorization")]
                 colorization_markers.push((value, style::Clear + foreground::Red));
             }
+            ASTBuildingError::CannotDestructure { pattern, value } => {
+                explanation = format!("The value {value} does not match the destructuring pattern {}.", pattern.bold());
+                #[cfg(feature = "colorization")]
+                colorization_markers.push((pattern, style::Clear + foreground::Red));
+            }
+            ASTBuildingError::RepeatedDestructuringName { pattern, name } => {
+                explanation = format!("The destructuring pattern {} binds the name {} more than once.", pattern.bold(), name.bold());
+                #[cfg(feature = "colorization")]
+                colorization_markers.push((name, style::Clear + foreground::Red));
+            }
             ASTBuildingError::CannotParseDecimal { value, lower_bound, upper_bound } => {
                 explanation = format!("Decimal Value {} is not a number between {lower_bound} and {upper_bound}.", value.bold());
                 #[cfg(feature = "colorization")]
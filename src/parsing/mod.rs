@@ -1,11 +1,14 @@
 use alloc::fmt::Debug;
+use alloc::format;
 use alloc::string::{String, ToString};
+use alloc::sync::Arc;
 use alloc::vec::Vec;
 use core::mem;
 
 
 
 use pest::iterators::Pair;
+use pest::Parser;
 use pest_derive::Parser;
 use simple_detailed_error::SimpleError;
 
@@ -13,12 +16,13 @@ use statement_parsing::WalkInput;
 
 use crate::engine::context::ContextBuilder;
 use crate::engine::Engine;
+use crate::parsing::error::ASTBuildingError;
 use crate::execution::ast::{Statement, AST};
 use crate::execution::RuntimeVariable;
-use crate::function::{MoonFunction, ToAbstractFunction};
-use crate::value::FullValue;
+use crate::function::{MoonFunction, Mutation, ToAbstractFunction};
+use crate::reduced_value_impl::CoercionPolicy;
+use crate::value::{FullValue, MoonValue};
 use crate::HashMap;
-use crate::HashSet;
 use crate::LazyLock;
 
 
@@ -30,27 +34,111 @@ pub mod error;
 #[grammar = "language_definition.pest"]
 pub(crate) struct SimpleParser;
 
+/// Words with a meaning in the language, these cannot be used as names of variables.
+pub(crate) const RESERVED_KEYWORDS: [&str; 15] = [
+    "let", "if", "else", "while", "return", "assert", "true", "false", "yes", "no", "null", "empty",
+    "in", "not", "use",
+];
+
+pub(crate) fn is_reserved_keyword(name: &str) -> bool {
+    RESERVED_KEYWORDS.contains(&name)
+}
+
+/// Checks if a name is a whole identifier that isn't a reserved keyword.
+pub(crate) fn is_valid_variable_name(name: &str) -> bool {
+    !is_reserved_keyword(name) && SimpleParser::parse(Rule::ident, name)
+        .is_ok_and(|parsed| parsed.as_str().len() == name.len())
+}
+
 #[derive(Clone, Debug)]
 pub(crate) struct FunctionInfo {
     can_inline_result: bool,
     function: MoonFunction,
     return_type_name: Option<String>,
+    doc: Option<Arc<str>>,
+    param_names: Option<Arc<[String]>>,
 }
 
 impl FunctionInfo {
-    pub(crate) fn new<Dummy, Params, ReturnValue, Function, AbstractFunction: ToAbstractFunction<Params, ReturnValue, Function, Dummy>>
-    (function: AbstractFunction) -> Self {
-        Self::new_raw(function.abstract_function())
-    }
-
     pub(crate) const fn new_raw(function: MoonFunction) -> Self {
-        Self { function, return_type_name: None, can_inline_result: false }
+        Self { function, return_type_name: None, can_inline_result: false, doc: None, param_names: None }
     }
 
     pub(crate) const fn inline(mut self) -> FunctionInfo {
         self.can_inline_result = true;
         self
     }
+
+    /// Describes this function for [Engine::functions], knowing under which name, module and type
+    /// it's registered.
+    pub(crate) fn describe<'engine>(&'engine self, name: &'engine str, module_name: Option<&'engine str>, associated_type_name: Option<&'engine str>) -> RegisteredFunction<'engine> {
+        RegisteredFunction {
+            name,
+            module_name,
+            associated_type_name,
+            number_of_params: self.number_of_params(),
+            inline: self.can_inline_result,
+            mutating: self.function.mutates_first_argument(),
+            return_type_name: self.return_type_name.as_deref(),
+            doc: self.doc.as_deref(),
+            param_names: self.param_names.as_deref(),
+        }
+    }
+
+    /// Number of arguments scripts give to this function.
+    pub(crate) fn number_of_params(&self) -> usize {
+        self.function.number_of_params()
+    }
+
+    /// Names of the types scripts know the parameters of this function by.
+    fn parameter_type_names(&self) -> Option<Vec<Option<&str>>> {
+        self.function.parameter_type_names()
+    }
+
+    /// Tells if both functions receive as many arguments of the same types, so they can't be
+    /// overloads of each other.
+    pub(crate) fn has_same_parameters(&self, other: &FunctionInfo) -> bool {
+        self.number_of_params() == other.number_of_params() && self.parameter_type_names() == other.parameter_type_names()
+    }
+
+    /// Tells if this function can receive arguments of these types, being [None] if it can't be
+    /// known until the script runs, kinds are compared with [CoercionPolicy::allows_kind].
+    pub(crate) fn accepts_argument_types(&self, argument_types: &[Option<String>], coercion_policy: CoercionPolicy) -> Option<bool> {
+        if self.number_of_params() != argument_types.len() {
+            return Some(false);
+        }
+        let mut is_known = true;
+        for (parameter_type, argument_type) in self.parameter_type_names()?.into_iter().zip(argument_types) {
+            let (Some(parameter_type), Some(argument_type)) = (parameter_type, argument_type) else {
+                is_known &= parameter_type.is_none();
+                continue;
+            };
+            match (MoonValueKind::built_in_kind_of(parameter_type), MoonValueKind::built_in_kind_of(argument_type)) {
+                (Some(expected), Some(found)) if !coercion_policy.allows_kind(&expected, &found) => return Some(false),
+                (None, None) if parameter_type != argument_type => return Some(false),
+                (Some(_), Some(_)) | (None, None) => {}
+                _ => is_known = false,
+            }
+        }
+        Some(true).filter(|_| is_known)
+    }
+
+    /// Describes the parameters of this function, like `(int, any)`, for telling overloads apart
+    /// on errors.
+    pub(crate) fn describe_parameters(&self) -> String {
+        match self.parameter_type_names() {
+            Some(type_names) => format!("({})", type_names.into_iter()
+                .map(|type_name| type_name.unwrap_or("any"))
+                .collect::<Vec<_>>().join(", ")),
+            None => format!("({} arguments)", self.number_of_params()),
+        }
+    }
+
+    /// Copy of this function that checks its arguments against the coercion policy of the script
+    /// calling it.
+    pub(crate) fn with_coercion_policy(&self, coercion_policy: CoercionPolicy) -> FunctionInfo {
+        FunctionInfo { function: self.function.with_coercion_policy(coercion_policy), ..self.clone() }
+    }
 }
 
 /// Builder pattern for defining custom Engine's functions
@@ -88,11 +176,12 @@ impl FunctionDefinition {
     /// ```
     pub fn new<Name: Into<String>, Dummy, Params, ReturnValue, Function, AbstractFunction: ToAbstractFunction<Params, ReturnValue, Function, Dummy>>
     (function_name: Name, function: AbstractFunction) -> Self {
-        let mut function_info = FunctionInfo::new_raw(function.abstract_function());
+        let function_name = function_name.into();
+        let mut function_info = FunctionInfo::new_raw(function.abstract_function().named(&function_name));
         function_info.return_type_name = MoonValueKind::get_kind_string_of::<ReturnValue>();
         Self {
             function_info: function_info,
-            function_name: function_name.into(),
+            function_name,
             module_name: None,
             associated_type_name: None,
         }
@@ -119,6 +208,20 @@ impl FunctionDefinition {
     ///     .associated_type_name("MyCustomTypeName");
     /// ```
     ///
+    /// The names of the built-in types (null, bool, int, decimal, string, array, map and function)
+    /// refer to said types, so this adds a method to every string:
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine, FunctionDefinition};
+    ///
+    /// let mut engine = Engine::new();
+    /// engine.add_function(FunctionDefinition::new("shout", |string: String| string.to_uppercase())
+    ///     .associated_type_name("string"));
+    ///
+    /// let result: String = engine.parse(r#"let name = "Jorge"; name.shout()"#, ContextBuilder::new())
+    ///     .unwrap().execute().unwrap().try_into().unwrap();
+    /// assert_eq!("JORGE", result);
+    /// ```
+    ///
     pub fn associated_type_name<'input, Name: Into<MoonValueKind<'input>>>(mut self, associated_type_name: Name) -> Self {
         self.associated_type_name = associated_type_name.into().get_moon_value_type().map(|string| string.to_string());
         self
@@ -153,6 +256,70 @@ impl FunctionDefinition {
         self
     }
 
+    /// Creates a function that mutates its first argument, it returns a [Mutation] with the new
+    /// value of said argument, which is written back into the variable it was read from, and the
+    /// actual result of the call.
+    ///
+    /// ```rust
+    /// use moon_script::{ContextBuilder, Engine, FunctionDefinition, Mutation};
+    /// let mut engine = Engine::new();
+    /// engine.add_function(FunctionDefinition::new_mutating("take_one", |count: i64| {
+    ///     Mutation { argument: count - 1, result: count }
+    /// }));
+    /// let result : Vec<i64> = engine.parse(r###"
+    ///     let count = 3;
+    ///     let taken = take_one(count);
+    ///     [taken, count]
+    /// "###, ContextBuilder::default()).unwrap().execute().unwrap().try_into().unwrap();
+    /// assert_eq!(vec![3, 2], result);
+    /// ```
+    pub fn new_mutating<Name: Into<String>, Dummy, Params, Argument, Output, Function, AbstractFunction: ToAbstractFunction<Params, Mutation<Argument, Output>, Function, Dummy>>
+    (function_name: Name, function: AbstractFunction) -> Self {
+        let mut definition = Self::new(function_name, function).mutating();
+        definition.function_info.return_type_name = MoonValueKind::get_kind_string_of::<Output>();
+        definition
+    }
+
+    /// Marks this function as one that mutates its first argument, see
+    /// [MoonFunction::mutating] for how these functions must return their values.
+    pub(crate) fn mutating(mut self) -> Self {
+        self.function_info.function = self.function_info.function.mutating();
+        self
+    }
+
+    /// Makes this function receive the random number generator state of the execution as its
+    /// first argument, see [MoonFunction::receiving_random_state].
+    pub(crate) fn receiving_random_state(mut self) -> Self {
+        self.function_info.function = self.function_info.function.receiving_random_state();
+        self
+    }
+
+    /// Describes what this function does, this is shown on the reference page written by
+    /// [Engine::generate_docs].
+    pub fn doc<Doc: Into<String>>(mut self, doc: Doc) -> Self {
+        self.function_info.doc = Some(Arc::from(doc.into()));
+        self
+    }
+
+    /// Names the arguments of this function for the reference page written by
+    /// [Engine::generate_docs], counting the value it's called on if it's associated to a type,
+    /// arguments left unnamed are shown as `arg1`, `arg2` and so on.
+    ///
+    /// ```rust
+    /// use moon_script::{DocsFormat, Engine, FunctionDefinition};
+    /// let mut engine = Engine::new();
+    /// engine.add_function(FunctionDefinition::new("heal", |hp: u16, amount: u16| hp + amount)
+    ///     .module_name("combat")
+    ///     .param_names(["hp", "amount"])
+    ///     .doc("Adds the amount to the hp."));
+    /// let docs = engine.generate_docs(DocsFormat::Markdown);
+    /// assert!(docs.contains("### `combat/heal(hp, amount) -> int`\n\nAdds the amount to the hp."));
+    /// ```
+    pub fn param_names<Names: IntoIterator<Item=Name>, Name: Into<String>>(mut self, param_names: Names) -> Self {
+        self.function_info.param_names = Some(param_names.into_iter().map(Into::into).collect());
+        self
+    }
+
     /// Specifies the type of the return value for this function, if let unmarked, associations
     /// cannot be used and therefore properties won't work.
     pub fn known_return_type_name<'input, Name: Into<MoonValueKind<'input>>>(mut self, return_type_name: Name) -> Self {
@@ -170,10 +337,36 @@ impl FunctionDefinition {
     }
 }
 
+/// Describes a function registered in an [Engine], as listed by [Engine::functions].
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegisteredFunction<'engine> {
+    /// Name used to call the function.
+    pub name: &'engine str,
+    /// Module of the function, if any, so it's called as `module/name()`.
+    pub module_name: Option<&'engine str>,
+    /// Type the function is associated to, if any, so it's called as `value.name()`.
+    pub associated_type_name: Option<&'engine str>,
+    /// Number of arguments the function receives, counting the value it's called on if it's
+    /// associated to a type.
+    pub number_of_params: usize,
+    /// If the result of the function is calculated when compiling the script when its arguments
+    /// are constant, see [FunctionDefinition::inline].
+    pub inline: bool,
+    /// If the function writes its first argument back, see [FunctionDefinition::mutating].
+    pub mutating: bool,
+    /// Name of the type of the values the function returns, if it's known.
+    pub return_type_name: Option<&'engine str>,
+    /// Description of the function, see [FunctionDefinition::doc].
+    pub doc: Option<&'engine str>,
+    /// Names of the arguments of the function, see [FunctionDefinition::param_names].
+    pub param_names: Option<&'engine [String]>,
+}
 
+#[derive(Debug, Clone, PartialEq)]
 struct Privatize;
 
 /// Types of Moon values
+#[derive(Debug, Clone, PartialEq)]
 pub enum MoonValueKind<'selflf> {
     Null,
     Boolean,
@@ -181,6 +374,7 @@ pub enum MoonValueKind<'selflf> {
     Decimal,
     String,
     Array,
+    Map,
     Function,
     Invalid,
     #[allow(private_interfaces)]
@@ -189,15 +383,6 @@ pub enum MoonValueKind<'selflf> {
     CustomString(String, Privatize),
 }
 
-static RESERVED_MOON_VALUE_KINDS: LazyLock<HashSet<String>> = LazyLock::new(|| {
-    [MoonValueKind::Null, MoonValueKind::Boolean, MoonValueKind::Integer,
-        MoonValueKind::Decimal, MoonValueKind::String, MoonValueKind::Array,
-        MoonValueKind::Function]
-        .map(|value_kind| value_kind.get_moon_value_type().unwrap().to_string())
-        .into_iter()
-        .collect::<HashSet<String>>()
-});
-
 pub(crate) static RUST_TYPES_TO_MOON_VALUE_KINDS: LazyLock<HashMap<&'static str, String>> = LazyLock::new(|| {
     [
         (core::any::type_name::<()>(), MoonValueKind::Null),
@@ -217,6 +402,7 @@ pub(crate) static RUST_TYPES_TO_MOON_VALUE_KINDS: LazyLock<HashMap<&'static str,
         (core::any::type_name::<f32>(), MoonValueKind::Decimal),
         (core::any::type_name::<f64>(), MoonValueKind::Decimal),
         (core::any::type_name::<String>(), MoonValueKind::String),
+        (core::any::type_name::<&str>(), MoonValueKind::String),
     ]
         .map(|(rust_type, moon_value_kind)| {
             (rust_type, moon_value_kind.get_moon_value_type().unwrap().to_string())
@@ -232,6 +418,26 @@ static RESULT_TYPE_PREFIX: LazyLock<String> = LazyLock::new(|| {
     result[0..result_start + 1].to_string()
 });
 
+static OPTION_TYPE_PREFIX: LazyLock<String> = LazyLock::new(|| {
+    let option = core::any::type_name::<Option<()>>();
+    let option_start = option.find(r"<").unwrap();
+    option[0..option_start + 1].to_string()
+});
+
+static MAP_TYPE_PREFIXES: LazyLock<[String; 2]> = LazyLock::new(|| {
+    [core::any::type_name::<HashMap<(), ()>>(), core::any::type_name::<alloc::collections::BTreeMap<(), ()>>()]
+        .map(|map| {
+            let map_start = map.find(r"<").unwrap();
+            map[0..map_start + 1].to_string()
+        })
+});
+
+static VEC_TYPE_PREFIX: LazyLock<String> = LazyLock::new(|| {
+    let vec = core::any::type_name::<Vec<()>>();
+    let vec_start = vec.find(r"<").unwrap();
+    vec[0..vec_start + 1].to_string()
+});
+
 fn decouple_ok_argument_from_its_result(type_in_use: &str) -> Option<&str> {
     if !type_in_use.starts_with(&*RESULT_TYPE_PREFIX) { return None; };
 
@@ -241,7 +447,7 @@ fn decouple_ok_argument_from_its_result(type_in_use: &str) -> Option<&str> {
         match char {
             '(' | '<' => opened_brackets_and_diamonds += 1,
             ')' | '>' => opened_brackets_and_diamonds -= 1,
-            ',' => return true,
+            ',' if opened_brackets_and_diamonds == 0 => return true,
             _ => {}
         }
         false
@@ -251,18 +457,46 @@ fn decouple_ok_argument_from_its_result(type_in_use: &str) -> Option<&str> {
 
 impl MoonValueKind<'_> {
     pub(crate) fn get_kind_string_of<T>() -> Option<String> {
+        Self::get_kind_string_of_type_name(core::any::type_name::<T>())
+    }
+
+    fn get_kind_string_of_type_name(type_name: &str) -> Option<String> {
+        if let Some(ok_type_name) = decouple_ok_argument_from_its_result(type_name) {
+            return Self::get_kind_string_of_type_name(ok_type_name);
+        }
+        if type_name.starts_with(&*OPTION_TYPE_PREFIX) {
+            return Self::get_kind_string_of_type_name(&type_name[OPTION_TYPE_PREFIX.len()..type_name.len() - 1]);
+        }
+        if type_name.eq(core::any::type_name::<MoonValue>()) {
+            return None;
+        }
         RUST_TYPES_TO_MOON_VALUE_KINDS
-            .get(core::any::type_name::<T>()).cloned()
-            .map(|string|
-                decouple_ok_argument_from_its_result(&string).map(|s| s.to_string()).unwrap_or(string)
-            )
+            .get(type_name).cloned()
+            .or_else(|| (type_name.starts_with(&*VEC_TYPE_PREFIX) || type_name.starts_with('['))
+                .then(|| MoonValueKind::Array.get_moon_value_type().unwrap().to_string()))
+            .or_else(|| MAP_TYPE_PREFIXES.iter().any(|prefix| type_name.starts_with(&**prefix))
+                .then(|| MoonValueKind::Map.get_moon_value_type().unwrap().to_string()))
             .or_else(||
-                MoonValueKind::from(core::any::type_name::<T>())
+                MoonValueKind::from(type_name)
                     .get_moon_value_type().map(|string| string.to_string())
             )
             .filter(|name| !name.eq("null"))
     }
 
+    pub(crate) fn built_in_kind_of(name: &str) -> Option<MoonValueKind<'static>> {
+        Some(match name {
+            "null" => MoonValueKind::Null,
+            "bool" => MoonValueKind::Boolean,
+            "int" => MoonValueKind::Integer,
+            "decimal" => MoonValueKind::Decimal,
+            "string" => MoonValueKind::String,
+            "array" => MoonValueKind::Array,
+            "map" => MoonValueKind::Map,
+            "function" => MoonValueKind::Function,
+            _ => return None,
+        })
+    }
+
     pub(crate) fn get_moon_value_type(&self) -> Option<&str> {
         Some(match self {
             MoonValueKind::Null => "null",
@@ -271,6 +505,7 @@ impl MoonValueKind<'_> {
             MoonValueKind::Decimal => "decimal",
             MoonValueKind::String => "string",
             MoonValueKind::Array => "array",
+            MoonValueKind::Map => "map",
             MoonValueKind::Function => "function",
             MoonValueKind::Invalid => return None,
             MoonValueKind::CustomStr(str, _) => str,
@@ -281,19 +516,13 @@ impl MoonValueKind<'_> {
 
 impl<'typename> From<&'typename str> for MoonValueKind<'typename> {
     fn from(value: &'typename str) -> Self {
-        if RESERVED_MOON_VALUE_KINDS.contains(value) {
-            return Self::Invalid;
-        }
-        Self::CustomStr(value, Privatize)
+        Self::built_in_kind_of(value).unwrap_or(Self::CustomStr(value, Privatize))
     }
 }
 
 impl From<String> for MoonValueKind<'_> {
     fn from(value: String) -> Self {
-        if RESERVED_MOON_VALUE_KINDS.contains(&value) {
-            return Self::Invalid;
-        }
-        Self::CustomString(value, Privatize)
+        Self::built_in_kind_of(&value).unwrap_or(Self::CustomString(value, Privatize))
     }
 }
 
@@ -398,8 +627,34 @@ fn optimize_variables(context: &mut ContextBuilder, inlineable_variables: Vec<(S
 
 pub(crate) fn build_ast<'input>(token: Pair<'input, Rule>, base: &Engine, mut context: ContextBuilder) -> Result<AST, Vec<SimpleError<'input>>> {
     if token.as_rule() != Rule::BASE_STATEMENTS {}
-    let statements_tokens = token.into_inner().next().unwrap();
+    let mut tokens = token.into_inner();
+    let mut used_modules = Vec::new();
+    let mut unknown_modules = Vec::new();
+    let statements_tokens = loop {
+        let token = tokens.next().unwrap();
+        if token.as_rule() != Rule::USE {
+            break token;
+        }
+        let module_name = token.into_inner().next().unwrap().as_str();
+        if base.has_module(module_name) {
+            used_modules.push(module_name.to_string());
+        } else {
+            unknown_modules.push(ASTBuildingError::ModuleNotFound { module: module_name }.into());
+        }
+    };
+    if !unknown_modules.is_empty() {
+        return Err(unknown_modules);
+    }
+    context.used_modules.splice(0..0, used_modules);
     context.started_parsing = true;
+    let invalid_variable_names = context.in_use_variables.iter()
+        .flat_map(|(_, variables)| variables.iter())
+        .filter(|variable| !is_valid_variable_name(&variable.name))
+        .map(|variable| ASTBuildingError::InvalidVariableName { variable_name: variable.name.clone() }.into())
+        .collect::<Vec<_>>();
+    if !invalid_variable_names.is_empty() {
+        return Err(invalid_variable_names);
+    }
     let inlineable_variables = context.in_use_variables.get(0).map(|(_, variables)| {
         variables.iter().enumerate()
             .filter(|(_, variable)| { variable.current_known_value.is_none() })
@@ -1,5 +1,5 @@
-use alloc::string::ToString;
-use alloc::vec;
+use alloc::string::{String, ToString};
+use alloc::{format, vec};
 use alloc::vec::Vec;
 
 use pest::iterators::Pair;
@@ -8,12 +8,14 @@ use simple_detailed_error::SimpleError;
 use crate::engine::context::{InputVariable, ContextBuilder};
 use crate::engine::Engine;
 use crate::execution::ast::Statement;
-use crate::execution::ConditionalStatements;
+use crate::execution::{ASTFunction, ConditionalStatements, RuntimeError};
 use crate::external_utils::on_error_iter::IterOnError;
+use crate::function::MoonFunction;
 use crate::parsing;
 use crate::parsing::{AddSourceOfError, Rule, value_parsing};
 use crate::parsing::error::ASTBuildingError;
 use crate::parsing::value_parsing::build_value_token;
+use crate::reduced_value_impl::CoercionPolicy;
 use crate::value::{FullValue, MoonValue};
 
 pub enum WalkInput<'selflf> {
@@ -45,6 +47,10 @@ fn walk_value<Action: FnMut(WalkInput)>(action: &mut Action, value: &mut FullVal
     action(WalkInput::Value(value));
     match value {
         FullValue::Array(values) => values.iter_mut().for_each(|value| walk_value(action, value)),
+        FullValue::Map(entries) => entries.iter_mut().for_each(|(key, value)| {
+            walk_value(action, key);
+            walk_value(action, value);
+        }),
         FullValue::Function(function) => function.args.iter_mut().for_each(|value| walk_value(action, value)),
         FullValue::Variable { .. } => {}
         _ => {}
@@ -69,7 +75,9 @@ pub fn build_token<'input>(token: Pair<'input, Rule>, base: &Engine, context: &m
             let mut pairs = token.into_inner();
             let predicate_pair = pairs.next().unwrap().into_inner().next().unwrap();
             let predicate_str = predicate_pair.as_str();
-            let predicate = build_value_token(predicate_pair, base, context).add_where_error(predicate_str, line_and_column)?;
+            let predicate = build_value_token(predicate_pair, base, context)
+                .and_then(|predicate| checked_predicate(predicate, predicate_str, "while", context.effective_coercion_policy()))
+                .add_where_error(predicate_str, line_and_column)?;
             context.push_block_level();
             let statements = parse_statements(pairs.next().unwrap(), base, context, false)?;
             context.pop_block_level();
@@ -79,6 +87,48 @@ pub fn build_token<'input>(token: Pair<'input, Rule>, base: &Engine, context: &m
             let value = build_value_token(token.into_inner().next().unwrap(), base, context).add_where_error(token_str, line_and_column)?;
             Ok(vec![Statement::ReturnCall(value)])
         }
+        Rule::ASSERT => {
+            let mut pairs = token.into_inner();
+            let predicate_pair = pairs.next().unwrap().into_inner().next().unwrap();
+            let predicate_str = predicate_pair.as_str();
+            let (line, column) = parsing::line_and_column_of_token(&predicate_pair, context);
+            let predicate = build_value_token(predicate_pair, base, context)
+                .and_then(|predicate| checked_predicate(predicate, predicate_str, "assert", context.effective_coercion_policy()))
+                .add_where_error(predicate_str, line_and_column)?;
+            let message = pairs.next()
+                .map(|message| build_value_token(message, base, context).add_where_error(token_str, line_and_column))
+                .transpose()?;
+
+            if predicate.is_simple_value() {
+                let predicate_holds: bool = TryFrom::try_from(predicate.resolve_value_no_context()).map_err(|_|
+                    vec![ASTBuildingError::ConditionDoestNotResolveToBoolean { predicate: predicate_str }.into()])
+                    .add_where_error(token_str, line_and_column)?;
+                if predicate_holds {
+                    return Ok(Vec::new());
+                }
+                let message = message
+                    .filter(|message| message.is_simple_value())
+                    .map(|message| assertion_message(message.resolve_value_no_context()));
+                return Err(vec![ASTBuildingError::AssertionAlwaysFails { predicate: predicate_str, message }.into()])
+                    .add_where_error(token_str, line_and_column);
+            }
+
+            let predicate_string = predicate_str.to_string();
+            let number_of_params = if message.is_some() { 2 } else { 1 };
+            let function = MoonFunction::from_fn(number_of_params, move |values| {
+                let predicate_holds: bool = values.next().ok_or(RuntimeError::AnArgumentIsMissing)??.try_into()
+                    .map_err(|_| RuntimeError::CannotTurnPredicateToBool {
+                        type_of_statement: "assert",
+                        function_error_message: format!("The predicate '{predicate_string}' doesn't resolve to a boolean value"),
+                    })?;
+                if predicate_holds {
+                    return Ok(MoonValue::Null);
+                }
+                let message = values.next().transpose()?.map(assertion_message);
+                Err(RuntimeError::AssertionFailed { predicate: predicate_string.clone(), message, line, column })
+            });
+            Ok(vec![Statement::FnCall(ASTFunction { function, args: [predicate].into_iter().chain(message).collect() })])
+        }
         Rule::IF_BLOCK => {
             let mut pairs = token.into_inner();
 
@@ -103,7 +153,9 @@ pub fn build_token<'input>(token: Pair<'input, Rule>, base: &Engine, context: &m
                     if first_predicate_str.is_none() {
                         first_predicate_str = Some(predicate_str);
                     }
-                    let predicate = build_value_token(predicate_pair, base, context).add_where_error(predicate_str, line_and_column)?;
+                    let predicate = build_value_token(predicate_pair, base, context)
+                        .and_then(|predicate| checked_predicate(predicate, predicate_str, "if", context.effective_coercion_policy()))
+                        .add_where_error(predicate_str, line_and_column)?;
                     parsed_statements.push(ConditionalStatements { condition: predicate, statements: Vec::new() })
                 } else {
                     context.push_block_level();
@@ -155,32 +207,12 @@ pub fn build_token<'input>(token: Pair<'input, Rule>, base: &Engine, context: &m
 
             match ident.as_rule() {
                 Rule::ident => {
-                    let value = build_value_token(pairs.next().unwrap(), &base, context).add_where_error(token_str, line_and_column)?;
-                    if value.is_simple_value() {
-                        let compiletime_variable_information = InputVariable {
-                            associated_type_name: value.type_name(context),
-                            name: ident.as_str().to_string(),
-                            current_known_value: Some(value.clone()),
-                            first_value: value,
-                            type_is_valid_up_to_depth: context.current_depth(),
-                            value_is_valid_up_to_depth: context.current_depth(),
-                            can_inline: true,
-                        };
-                        context.push_variable_internal(compiletime_variable_information, declare_variable_as_new);
-                        Ok(Vec::new())
-                    } else {
-                        let compiletime_variable_information = InputVariable {
-                            associated_type_name: value.type_name(context),
-                            name: ident.as_str().to_string(),
-                            current_known_value: None,
-                            first_value: FullValue::Null,
-                            type_is_valid_up_to_depth: context.current_depth(),
-                            value_is_valid_up_to_depth: context.current_depth(),
-                            can_inline: false,
-                        };
-                        let (block_level, var_index) = context.push_variable_internal(compiletime_variable_information, declare_variable_as_new);
-                        Ok(vec![Statement::UnoptimizedAssignament { block_level, var_index, value }])
+                    if parsing::is_reserved_keyword(ident.as_str()) {
+                        return Err(vec![ASTBuildingError::ReservedKeyword { keyword: ident.as_str() }.into()])
+                            .add_where_error(token_str, line_and_column);
                     }
+                    let value = build_value_token(pairs.next().unwrap(), &base, context).add_where_error(token_str, line_and_column)?;
+                    Ok(assign_variable(ident.as_str().to_string(), value, declare_variable_as_new, context))
                 }
                 Rule::property => {
                     let value = build_value_token(pairs.next().unwrap(), &base, context).add_where_error(token_str, line_and_column)?;
@@ -196,6 +228,60 @@ pub fn build_token<'input>(token: Pair<'input, Rule>, base: &Engine, context: &m
                 _ => { unreachable!() }
             }
         }
+        Rule::DESTRUCTURING_ASSIGNMENT => {
+            let mut pairs = token.into_inner();
+            let pattern_token = pairs.next().unwrap();
+            let pattern = pattern_token.as_str();
+            let is_map_pattern = pattern_token.as_rule() == Rule::DESTRUCTURING_MAP;
+            let idents = pattern_token.into_inner().map(|ident| ident.as_str()).collect::<Vec<_>>();
+            if let Some(keyword) = idents.iter().find(|ident| parsing::is_reserved_keyword(ident)) {
+                return Err(vec![ASTBuildingError::ReservedKeyword { keyword }.into()])
+                    .add_where_error(token_str, line_and_column);
+            }
+            if let Some(name) = idents.iter().enumerate().find(|(index, name)| idents[..*index].contains(name)).map(|(_, name)| *name) {
+                return Err(vec![ASTBuildingError::RepeatedDestructuringName { pattern, name }.into()]).add_where_error(token_str, line_and_column);
+            }
+            let names = idents.into_iter().map(String::from).collect::<Vec<_>>();
+            let value = build_value_token(pairs.next().unwrap(), base, context).add_where_error(token_str, line_and_column)?;
+
+            if value.is_simple_value() {
+                let value = value.resolve_value_no_context();
+                let values = destructure(value.clone(), is_map_pattern, &names)
+                    .ok_or_else(|| vec![ASTBuildingError::CannotDestructure { pattern, value }.into()])
+                    .add_where_error(token_str, line_and_column)?;
+                let statements = names.into_iter().zip(values)
+                    .flat_map(|(name, value)| assign_variable(name, FullValue::from(value), true, context))
+                    .collect();
+                return Ok(statements);
+            }
+
+            // The value is destructured once into a hidden variable holding the values of each name,
+            // its name contains spaces and the slot it's stored at, so it never collides
+            let (block_level, block_variables) = context.in_use_variables.last().unwrap();
+            let hidden_name = format!("destructured values {block_level} {}", block_variables.len());
+            let (pattern, destructured_names) = (pattern.to_string(), names.clone());
+            let destructuring = MoonFunction::from_fn(1, move |values| {
+                let value = values.next().ok_or(RuntimeError::AnArgumentIsMissing)??;
+                destructure(value.clone(), is_map_pattern, &destructured_names)
+                    .map(MoonValue::Array)
+                    .ok_or_else(|| RuntimeError::CannotDestructure { pattern: pattern.clone(), value })
+            });
+            let value = FullValue::Function(ASTFunction { function: destructuring, args: vec![value] });
+            let mut statements = assign_variable(hidden_name, value, true, context);
+            let Some(Statement::UnoptimizedAssignament { block_level, var_index, .. }) = statements.last() else {
+                unreachable!("Non constant values are always assigned through a statement")
+            };
+            let destructured_values = FullValue::Variable { block_level: *block_level, var_index: *var_index };
+            for (index, name) in names.into_iter().enumerate() {
+                let function = MoonFunction::from_fn(1, move |values| match values.next().ok_or(RuntimeError::AnArgumentIsMissing)?? {
+                    MoonValue::Array(values) => values.into_iter().nth(index).ok_or(RuntimeError::AnArgumentIsMissing),
+                    _ => Err(RuntimeError::AnArgumentIsMissing),
+                });
+                let value = FullValue::Function(ASTFunction { function, args: vec![destructured_values.clone()] });
+                statements.extend(assign_variable(name, value, true, context));
+            }
+            Ok(statements)
+        }
         Rule::fncall => {
             let function = build_value_token(token, base, context).add_where_error(token_str, line_and_column)?;
             Ok(match function {
@@ -227,6 +313,82 @@ pub fn build_token<'input>(token: Pair<'input, Rule>, base: &Engine, context: &m
     res
 }
 
+/// Registers the variable on the context, returning the statements required to assign it at
+/// runtime, none if its value is known at compile time.
+fn assign_variable(name: String, value: FullValue, declare_new: bool, context: &mut ContextBuilder) -> Vec<Statement> {
+    let assigns_runtime_variable = !declare_new && context.find_variable(&name).is_some_and(|(_, _, variable)| !variable.can_inline);
+    if value.is_simple_value() && !assigns_runtime_variable {
+        let compiletime_variable_information = InputVariable {
+            associated_type_name: value.type_name(context),
+            name,
+            current_known_value: Some(value.clone()),
+            first_value: value,
+            type_is_valid_up_to_depth: context.current_depth(),
+            value_is_valid_up_to_depth: context.current_depth(),
+            can_inline: true,
+        };
+        context.push_variable_internal(compiletime_variable_information, declare_new);
+        Vec::new()
+    } else {
+        let compiletime_variable_information = InputVariable {
+            associated_type_name: value.type_name(context),
+            name,
+            current_known_value: None,
+            first_value: FullValue::Null,
+            type_is_valid_up_to_depth: context.current_depth(),
+            value_is_valid_up_to_depth: context.current_depth(),
+            can_inline: false,
+        };
+        let (block_level, var_index) = context.push_variable_internal(compiletime_variable_information, declare_new);
+        vec![Statement::UnoptimizedAssignament { block_level, var_index, value }]
+    }
+}
+
+/// Makes a predicate fail if the value it resolves to can't be used as a boolean under the
+/// coercion policy, predicates known at compile time are checked right away.
+fn checked_predicate<'input>(predicate: FullValue, predicate_str: &'input str, type_of_statement: &'static str, coercion_policy: CoercionPolicy) -> Result<FullValue, Vec<SimpleError<'input>>> {
+    if coercion_policy == CoercionPolicy::Lenient {
+        return Ok(predicate);
+    }
+    if predicate.is_simple_value() {
+        return coercion_policy.predicate_to_bool(predicate.resolve_value_no_context())
+            .map(FullValue::Boolean)
+            .map_err(|_| vec![ASTBuildingError::ConditionDoestNotResolveToBoolean { predicate: predicate_str }.into()]);
+    }
+    let predicate_string = predicate_str.to_string();
+    let function = MoonFunction::from_fn(1, move |values| {
+        let value = values.next().ok_or(RuntimeError::AnArgumentIsMissing)??;
+        coercion_policy.predicate_to_bool(value)
+            .map(MoonValue::Boolean)
+            .map_err(|value| RuntimeError::CannotTurnPredicateToBool {
+                type_of_statement,
+                function_error_message: format!("The predicate '{predicate_string}' resolved to {value}, which isn't a boolean value"),
+            })
+    });
+    Ok(FullValue::Function(ASTFunction { function, args: vec![predicate] }))
+}
+
+fn assertion_message(message: MoonValue) -> String {
+    match message {
+        MoonValue::String(message) => message,
+        message => message.to_string(),
+    }
+}
+
+/// Splits the value into the values of each of the names of a destructuring pattern, arrays must
+/// have exactly as many elements as names, while maps must contain a string key for each name.
+fn destructure(value: MoonValue, is_map_pattern: bool, names: &[String]) -> Option<Vec<MoonValue>> {
+    match value {
+        MoonValue::Array(values) if !is_map_pattern && values.len() == names.len() => Some(values),
+        MoonValue::Map(entries) if is_map_pattern => names.iter().map(|name| {
+            entries.iter()
+                .find(|(key, _)| matches!(key, MoonValue::String(key) if key == name))
+                .map(|(_, value)| value.clone())
+        }).collect(),
+        _ => None,
+    }
+}
+
 fn parse_statements<'input>(token: Pair<'input, Rule>, base: &Engine, context: &mut ContextBuilder, last_statement_is_final_statement: bool) -> Result<Vec<Statement>, Vec<SimpleError<'input>>> {
     let mut errors = Vec::new();
     let statements_token = token.into_inner();
@@ -12,25 +12,31 @@ use simple_detailed_error::{SimpleError, SimpleErrorDetail};
 
 use crate::engine::context::ContextBuilder;
 use crate::engine::Engine;
-use crate::execution::ASTFunction;
+use crate::execution::{ASTFunction, RuntimeError};
 use crate::external_utils::on_error_iter::IterOnError;
-use crate::function::ToAbstractFunction;
+use crate::function::{MoonFunction, ToAbstractFunction};
 use crate::parsing::error::ASTBuildingError;
+use crate::parsing;
 use crate::parsing::{FunctionInfo, Rule};
+use crate::reduced_value_impl::{impl_operators, CoercionPolicy};
 use crate::value::{FullValue, MoonValue};
 use crate::LazyLock;
 
 static BINARY_OPERATION_PARSER: LazyLock<PrattParser<Rule>> = LazyLock::new(||{
+    let shift_ops = Op::infix(Rule::slf, Assoc::Left) | Op::infix(Rule::srg, Assoc::Left);
     let sums_ops = Op::infix(Rule::sum, Assoc::Left) | Op::infix(Rule::sub, Assoc::Left);
     let mul_ops = Op::infix(Rule::mul, Assoc::Left) | Op::infix(Rule::div, Assoc::Left) | Op::infix(Rule::rem, Assoc::Left);
     let comparators_ops = Op::infix(Rule::eq, Assoc::Left) | Op::infix(Rule::neq, Assoc::Left)
         | Op::infix(Rule::gt, Assoc::Left) | Op::infix(Rule::gte, Assoc::Left)
-        | Op::infix(Rule::lt, Assoc::Left) | Op::infix(Rule::lte, Assoc::Left);
+        | Op::infix(Rule::lt, Assoc::Left) | Op::infix(Rule::lte, Assoc::Left)
+        | Op::infix(Rule::in_op, Assoc::Left) | Op::infix(Rule::not_in, Assoc::Left);
     let logic_gate_comparators = Op::infix(Rule::or, Assoc::Left) | Op::infix(Rule::xor, Assoc::Left)
         | Op::infix(Rule::and, Assoc::Left);
     PrattParser::new()
+        .op(Op::infix(Rule::coalesce, Assoc::Left))
         .op(logic_gate_comparators)
         .op(comparators_ops)
+        .op(shift_ops)
         .op(sums_ops)
         .op(mul_ops)
 });
@@ -44,14 +50,28 @@ pub fn build_value_token<'input>(mut token: Pair<'input, Rule>, base: &Engine, c
     log::trace!("Parsing complex token {token_rule:?} = {token_str}");
     let res = match token.as_rule() {
         Rule::BINARY_OPERATION => {
+            let coercion_policy = context.effective_coercion_policy();
+            let used_modules = context.used_modules.clone();
             let res = BINARY_OPERATION_PARSER
                 .map_primary(|primary| {
+                    let variable = variable_of_token(&primary, context);
                     build_value_token(primary, base, context)
+                        .map(|value| {
+                            let type_name = operand_type_name(variable, &value, context);
+                            (value, type_name)
+                        })
                 })
                 .map_infix(|lhs, op, rhs| {
-                    let operator = op.as_str();
+                    let operator = match op.as_rule() {
+                        Rule::not_in => "not in",
+                        _ => op.as_str(),
+                    };
                     log::trace!("Found op {operator} left {lhs:?}, right {rhs:?}");
-                    let function = base.find_binary_operator(operator);
+                    let function = base.find_binary_operator(operator)
+                        .map(|function| FunctionInfo {
+                            function: impl_operators::with_operand_policy(operator, function.function.clone(), coercion_policy),
+                            ..function.clone()
+                        });
 
                     if function.is_none() || lhs.is_err() || rhs.is_err() {
                         let mut error_union = lhs.err().unwrap_or_default();
@@ -61,9 +81,30 @@ pub fn build_value_token<'input>(mut token: Pair<'input, Rule>, base: &Engine, c
                         }
                         return Err(error_union);
                     }
-                    let (lhs, rhs, function) = (lhs.unwrap(), rhs.unwrap(), function.unwrap());
+                    let ((lhs, lhs_type), (rhs, rhs_type), function) = (lhs.unwrap(), rhs.unwrap(), function.unwrap());
+                    let (function, is_overload) = overloaded_operator(function, base.binary_operator_overloads(operator), &[lhs_type.clone(), rhs_type.clone()], coercion_policy);
+                    let type_name = if is_overload { function.return_type_name.clone() } else { None };
 
-                    Ok(if function.can_inline_result && lhs.is_simple_value() && rhs.is_simple_value() {
+                    if !is_overload && matches!(op.as_rule(), Rule::in_op | Rule::not_in) {
+                        let associated_contains = match rhs_type {
+                            Some(rhs_type) => {
+                                let overloads = find_overloads(base, &used_modules, Some(&rhs_type), None, "contains")?;
+                                choose_overload("contains", overloads, Some(&[Some(rhs_type), lhs_type]), coercion_policy)?
+                            }
+                            None => None,
+                        };
+                        if let Some(associated_contains) = associated_contains {
+                            let mut is_contained = decompress_function("contains", vec![rhs, lhs], &associated_contains)?;
+                            if op.as_rule() == Rule::not_in {
+                                let negation = base.find_unary_operator("!")
+                                    .ok_or_else(|| vec![ASTBuildingError::OperatorNotFound { operator: "!" }.to_simple_error()])?;
+                                is_contained = decompress_function("!", vec![is_contained], negation)?;
+                            }
+                            return Ok((is_contained, None));
+                        }
+                    }
+
+                    Ok((if function.can_inline_result && lhs.is_simple_value() && rhs.is_simple_value() {
                         let (lhs, rhs) = (lhs.resolve_value_no_context(), rhs.resolve_value_no_context());
                         FullValue::from(
                             function.function.execute_into_iter([Ok(lhs), Ok(rhs)].into_iter())
@@ -71,18 +112,21 @@ pub fn build_value_token<'input>(mut token: Pair<'input, Rule>, base: &Engine, c
                         )
                     } else {
                         FullValue::Function(ASTFunction { function: function.function.clone(), args: vec![lhs, rhs] })
-                    })
+                    }, type_name))
                 })
                 .parse(token.into_inner());
-            res.clone()
+            res.map(|(value, _)| value)
         }
         Rule::UNARY_OPERATION => {
             let mut token = token.into_inner();
             let operator = token.next().unwrap().as_str();
             let value = token.next().unwrap();
+            let variable = variable_of_token(&value, context);
             let value = build_value_token(value, base, context)?;
             let function = base.find_unary_operator(operator)
                 .ok_or_else(|| vec![ASTBuildingError::OperatorNotFound { operator }.at(token_str)])?;
+            let value_type = operand_type_name(variable, &value, context);
+            let (function, _) = overloaded_operator(function.clone(), base.unary_operator_overloads(operator), &[value_type], context.effective_coercion_policy());
             Ok(if function.can_inline_result && value.is_simple_value() {
                 let reduced_value = value.resolve_value_no_context();
                 FullValue::from(
@@ -111,26 +155,53 @@ pub fn build_value_token<'input>(mut token: Pair<'input, Rule>, base: &Engine, c
                         }
                     }).abstract_function(),
                     return_type_name: None,
+                    doc: None,
+                    param_names: None,
                 };
                 value = decompress_function("array_access", vec![value, FullValue::from(MoonValue::from(index))], &array_access_function)?;
             }
             Ok(value)
         }
         Rule::ARRAY => {
+            let values = build_spreadable_values(token.into_inner(), base, context)?;
+            if values.iter().any(|(_, is_spread)| *is_spread) {
+                Ok(join_spread_values(values)?)
+            } else {
+                Ok(FullValue::Array(values.into_iter().map(|(value, _)| value).collect()))
+            }
+        }
+        Rule::MAP => {
             let mut errors = Vec::new();
-            let res = token.into_inner().map(|pair| build_value_token(pair, base, context))
-                .on_errors(|error| errors.extend(error.into_iter()))
+            let res = token.into_inner()
+                .map(|entry| {
+                    let mut key_and_value = entry.into_inner();
+                    let key = key_and_value.next().unwrap();
+                    let key = match key.as_rule() {
+                        Rule::map_key => Ok(FullValue::String(key.as_str().to_string())),
+                        _ => build_value_token(key, base, context),
+                    };
+                    let value = build_value_token(key_and_value.next().unwrap(), base, context);
+                    match (key, value) {
+                        (Ok(key), Ok(value)) => Ok((key, value)),
+                        (key, value) => {
+                            let mut error_union = key.err().unwrap_or_default();
+                            error_union.extend(value.err().unwrap_or_default());
+                            Err(error_union)
+                        }
+                    }
+                })
+                .on_errors(|error| errors.extend(error))
                 .collect();
             if !errors.is_empty() {
                 return Err(errors);
             }
-            Ok(FullValue::Array(res))
+            Ok(FullValue::Map(res))
         }
         Rule::fncall => {
-            let mut errors = Vec::new();
             let mut token = token.into_inner();
             let mut object: Option<FullValue> = None;
             let mut object_type : Option<String> = None;
+            let mut receiver = None;
             let mut module = None;
             let function_name: &str;
             loop {
@@ -144,11 +215,13 @@ pub fn build_value_token<'input>(mut token: Pair<'input, Rule>, base: &Engine, c
                                 let value = FullValue::Variable { block_level, var_index };
                                 (type_name, value)
                             })
-                            .or_else(|| base.constants().get(current_token_as_str)
+                            .or_else(|| base.constant(current_token_as_str)
                                 .map(|constant| (constant.type_name.clone(), FullValue::from(constant.value.clone())))
                             )
                             .ok_or_else(|| vec![ASTBuildingError::VariableNotInScope { variable_name: current_token_as_str }.into()])?;
                         object = Some(t_object);
+                        receiver = context.find_variable(current_token_as_str)
+                            .map(|(block_level, var_index, _)| (block_level, var_index));
                         object_type = Some(t_object_type
                             .ok_or_else(|| vec![ASTBuildingError::CouldntInlineVariableOfUnknownType { variable_name: current_token_as_str }.into()])?
                         );
@@ -161,26 +234,41 @@ pub fn build_value_token<'input>(mut token: Pair<'input, Rule>, base: &Engine, c
                     _ => { panic!() }
                 }
             }
-            let mut args = token
-                .map(|argument| build_value_token(argument, base, context))
-                .on_errors(|error| errors.extend(error.into_iter()))
-                .collect::<Vec<_>>();
+            let argument_tokens = token.collect::<Vec<_>>();
+            if object.is_none() {
+                receiver = argument_tokens.first().and_then(|argument| variable_of_token(argument, context));
+            }
+            let mut args = build_spreadable_values(argument_tokens.into_iter(), base, context)?;
             if let Some(variable) = object {
-                args.insert(0, variable);
+                args.insert(0, (variable, false));
+            }
+            let mut argument_types = argument_types(&args, context);
+            if let (Some(argument_types), Some(object_type)) = (argument_types.as_mut(), object_type.as_ref()) {
+                argument_types[0] = Some(object_type.clone());
             }
-            let function = base.find_function(object_type.clone(), module, function_name)
+            let overloads = find_overloads(base, &context.used_modules, object_type.as_deref(), module, function_name)?;
+            let function = choose_overload(function_name, overloads, argument_types.as_deref(), context.effective_coercion_policy())?
                 .ok_or_else(|| vec![ASTBuildingError::FunctionNotFound { function_name, associated_to_type: object_type.clone(), module }.into()])?;
-            Ok(decompress_function(function_name, args, function)?)
+            if args.iter().any(|(_, is_spread)| *is_spread) {
+                let spreading_function = FunctionInfo { function: function.function.spreading_arguments(), ..function.clone() };
+                Ok(decompress_function(function_name, vec![join_spread_values(args)?], &spreading_function)?)
+            } else {
+                let mut args = args.into_iter().map(|(arg, _)| arg).collect::<Vec<_>>();
+                prepare_mutation_target(&function.function, &mut args, receiver, context);
+                Ok(decompress_function(function_name, args, &function)?)
+            }
         }
         Rule::ident => {
             let ident = token.as_str();
-            if let Some((block_level, var_index, variable)) = context.find_variable(ident) {
+            if parsing::is_reserved_keyword(ident) {
+                Err(vec![ASTBuildingError::ReservedKeyword { keyword: ident }.into()])
+            } else if let Some((block_level, var_index, variable)) = context.find_variable(ident) {
                 Ok(if variable.inlineable_value().is_some_and(|known_value| known_value.is_simple_value()) {
                     variable.inlineable_value().unwrap()
                 } else {
                     FullValue::Variable { block_level, var_index }
                 })
-            } else if let Some(value) = base.constants().get(ident) {
+            } else if let Some(value) = base.constant(ident) {
                 Ok(FullValue::from(value.value.clone()))
             } else {
                 Err(vec![ASTBuildingError::VariableNotInScope { variable_name: ident }.to_simple_error()])
@@ -205,11 +293,222 @@ pub fn build_value_token<'input>(mut token: Pair<'input, Rule>, base: &Engine, c
     res
 }
 
+/// Gets the type of a value as known at compile time, this includes the associated type of
+/// variables whose value is unknown.
+fn known_type_name(value: &FullValue, context: &mut ContextBuilder) -> Option<String> {
+    match value {
+        FullValue::Variable { block_level, var_index } => context.get_variable_at(*block_level, *var_index)
+            .and_then(|variable| variable.associated_type_name.clone()),
+        FullValue::Function(_) => None,
+        value => value.type_name(context),
+    }
+}
+
+/// Gets the types of the arguments of a call as known at compile time, [None] if any of them is
+/// spread, as it's unknown how many arguments there will be.
+fn argument_types(args: &[(FullValue, bool)], context: &mut ContextBuilder) -> Option<Vec<Option<String>>> {
+    if args.iter().any(|(_, is_spread)| *is_spread) {
+        return None;
+    }
+    Some(args.iter().map(|(arg, _)| known_type_name(arg, context)).collect())
+}
+
+/// Gets the type of an operand, being the type associated to the variable it names, if it's just
+/// the name of a variable, as its value might have been inlined, losing said type.
+fn operand_type_name(variable: Option<(usize, usize)>, value: &FullValue, context: &mut ContextBuilder) -> Option<String> {
+    variable
+        .and_then(|(block_level, var_index)| context.get_variable_at(block_level, var_index)?.associated_type_name.clone())
+        .or_else(|| known_type_name(value, context))
+}
+
+/// Gets the variable a value refers to, if said value is just the name of a variable.
+fn variable_of_token(token: &Pair<Rule>, context: &mut ContextBuilder) -> Option<(usize, usize)> {
+    let mut token = token.clone();
+    while token.as_rule() == Rule::VALUE {
+        let mut inner_tokens = token.into_inner();
+        token = inner_tokens.next()?;
+        if inner_tokens.next().is_some() {
+            return None;
+        }
+    }
+    if token.as_rule() != Rule::ident {
+        return None;
+    }
+    context.find_variable(token.as_str()).map(|(block_level, var_index, _)| (block_level, var_index))
+}
+
+/// If the function mutates its first argument and said argument is a variable, the variable is
+/// turned into a runtime one, so the function can write the mutated value back into it.
+fn prepare_mutation_target(function: &MoonFunction, args: &mut [FullValue], receiver: Option<(usize, usize)>, context: &mut ContextBuilder) {
+    let Some((block_level, var_index)) = receiver.filter(|_| function.mutates_first_argument()) else { return; };
+    let Some(variable) = context.get_variable_at(block_level, var_index) else { return; };
+    if let Some(known_value) = variable.inlineable_value() {
+        variable.first_value = known_value;
+    }
+    variable.can_inline = false;
+    variable.current_known_value = None;
+    if let Some(first_arg) = args.first_mut() {
+        *first_arg = FullValue::Variable { block_level, var_index };
+    }
+}
+
+/// Builds the values of an array or the arguments of a call, marking the ones that are spread
+/// like '...values'.
+fn build_spreadable_values<'input>(tokens: impl Iterator<Item=Pair<'input, Rule>>, base: &Engine, context: &mut ContextBuilder) -> Result<Vec<(FullValue, bool)>, Vec<SimpleError<'input>>> {
+    let mut errors = Vec::new();
+    let values = tokens
+        .map(|token| match token.as_rule() {
+            Rule::SPREAD => build_value_token(token.into_inner().next().unwrap(), base, context).map(|value| (value, true)),
+            _ => build_value_token(token, base, context).map(|value| (value, false)),
+        })
+        .on_errors(|error| errors.extend(error))
+        .collect();
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+    Ok(values)
+}
+
+/// Joins values into a single array, where the spread values are concatenated instead of being
+/// added as a single element, this is folded if all the values are known at compile time.
+fn join_spread_values(values: Vec<(FullValue, bool)>) -> Result<FullValue, Vec<SimpleError<'static>>> {
+    let mut arrays = Vec::new();
+    for (value, is_spread) in values {
+        match arrays.last_mut() {
+            _ if is_spread => arrays.push(value),
+            Some(FullValue::Array(array)) => array.push(value),
+            _ => arrays.push(FullValue::Array(vec![value])),
+        }
+    }
+    let number_of_arrays = arrays.len();
+    let concat_function = FunctionInfo::new_raw(MoonFunction::from_fn(number_of_arrays, |arrays| {
+        let mut values = Vec::new();
+        for array in arrays {
+            match array? {
+                MoonValue::Array(array) => values.extend(array),
+                value => return Err(RuntimeError::CannotSpread { value }),
+            }
+        }
+        Ok(MoonValue::Array(values))
+    })).inline();
+    decompress_function("...", arrays, &concat_function)
+}
+
+/// Chooses the overload of an operator for the types of its operands, see
+/// [Engine::add_binary_operator], telling if one was chosen, when the type of an operand isn't
+/// known the choice is left for when the script runs.
+fn overloaded_operator(function: FunctionInfo, overloads: Vec<(&str, &FunctionInfo)>, operand_types: &[Option<String>], coercion_policy: CoercionPolicy) -> (FunctionInfo, bool) {
+    if overloads.is_empty() {
+        return (function, false);
+    }
+    let known_overload = operand_types.iter().flatten()
+        .find_map(|operand_type| overloads.iter().find(|(type_name, _)| type_name == operand_type));
+    if let Some((_, overload)) = known_overload {
+        return (overload.with_coercion_policy(coercion_policy), true);
+    }
+    // Values don't carry custom types when running, so only overloads for built-in types can be
+    // told apart then
+    let overloads = overloads.into_iter()
+        .filter_map(|(type_name, overload)| Some((parsing::MoonValueKind::built_in_kind_of(type_name)?, overload)))
+        .collect::<Vec<_>>();
+    if overloads.is_empty() || operand_types.iter().all(Option::is_some) {
+        return (function, false);
+    }
+    let can_inline_result = function.can_inline_result && overloads.iter().all(|(_, overload)| overload.can_inline_result);
+    let overloads = overloads.into_iter()
+        .map(|(kind, overload)| (kind, overload.with_coercion_policy(coercion_policy.for_overloads()).function))
+        .collect();
+    let function = FunctionInfo {
+        function: impl_operators::dispatching_overloads(function.function, overloads),
+        can_inline_result,
+        return_type_name: None,
+        ..function
+    };
+    (function, false)
+}
+
+/// Finds every overload of the function a script calls, failing if it doesn't name a module and
+/// many modules have it.
+fn find_overloads<'input>(base: &Engine, used_modules: &[String], type_name: Option<&str>, module: Option<&str>, function_name: &str) -> Result<Vec<FunctionInfo>, Vec<SimpleError<'input>>> {
+    base.find_function(type_name, module, function_name, used_modules)
+        .map(|overloads| overloads.into_iter().cloned().collect())
+        .map_err(|modules| vec![ASTBuildingError::AmbiguousFunction {
+            function_name: function_name.to_string(),
+            associated_to_type: type_name.map(ToString::to_string),
+            modules: modules.into_iter().map(ToString::to_string).collect(),
+        }.into()])
+}
+
+/// Chooses the overload of a function for the types of its arguments, see [Engine::add_function],
+/// using the [CoercionPolicy] of the script, [None] if the function has no overloads.
+///
+/// Only the overloads receiving as many arguments as given are considered, if any does, so the
+/// kinds of the arguments only tell apart overloads of the same arity. The argument types are
+/// [None] when the arguments are spread, leaving the choice for when the script runs, as it is
+/// too when the known types don't tell which overload to use.
+fn choose_overload<'input>(function_name: &str, overloads: Vec<FunctionInfo>, argument_types: Option<&[Option<String>]>, coercion_policy: CoercionPolicy) -> Result<Option<FunctionInfo>, Vec<SimpleError<'input>>> {
+    let overloads = match argument_types {
+        Some(argument_types) if overloads.len() > 1 => {
+            let same_arity = overloads.iter()
+                .filter(|overload| overload.number_of_params() == argument_types.len())
+                .cloned()
+                .collect::<Vec<_>>();
+            if same_arity.is_empty() { overloads } else { same_arity }
+        }
+        _ => overloads,
+    };
+    let argument_types = match (overloads.as_slice(), argument_types) {
+        ([], _) => return Ok(None),
+        ([function], _) => return Ok(Some(function.with_coercion_policy(coercion_policy))),
+        (_, None) => return Ok(Some(dispatching_overload(function_name, overloads.iter().collect(), coercion_policy))),
+        (_, Some(argument_types)) => argument_types,
+    };
+    let acceptances = overloads.iter()
+        .map(|overload| (overload, overload.accepts_argument_types(argument_types, coercion_policy.for_overloads())))
+        .filter(|(_, accepts)| *accepts != Some(false))
+        .collect::<Vec<_>>();
+    let accepting = acceptances.iter().map(|(overload, _)| *overload).collect::<Vec<_>>();
+    match acceptances.as_slice() {
+        [] => Err(vec![ASTBuildingError::NoMatchingOverload {
+            function_name: function_name.to_string(),
+            argument_types: argument_types.to_vec(),
+            overloads: overloads.iter().map(FunctionInfo::describe_parameters).collect(),
+        }.into()]),
+        [(overload, _)] => Ok(Some(overload.with_coercion_policy(coercion_policy))),
+        _ if acceptances.iter().all(|(_, accepts)| accepts.is_some()) => Err(vec![ambiguous_overload(function_name, &accepting, argument_types)]),
+        _ => Ok(Some(dispatching_overload(function_name, accepting, coercion_policy))),
+    }
+}
+
+/// Joins overloads into a function choosing between them when the script runs, trying them in
+/// the order they were added.
+fn dispatching_overload(function_name: &str, overloads: Vec<&FunctionInfo>, coercion_policy: CoercionPolicy) -> FunctionInfo {
+    let first_overload = overloads[0];
+    let return_type_name = first_overload.return_type_name.clone()
+        .filter(|return_type_name| overloads.iter().all(|overload| overload.return_type_name.as_ref() == Some(return_type_name)));
+    FunctionInfo {
+        function: MoonFunction::dispatching(function_name, overloads.iter()
+            .map(|overload| overload.with_coercion_policy(coercion_policy.for_overloads()).function)
+            .collect()),
+        can_inline_result: overloads.iter().all(|overload| overload.can_inline_result),
+        return_type_name,
+        ..first_overload.clone()
+    }
+}
+
+fn ambiguous_overload<'input>(function_name: &str, overloads: &[&FunctionInfo], argument_types: &[Option<String>]) -> SimpleError<'input> {
+    ASTBuildingError::AmbiguousOverload {
+        function_name: function_name.to_string(),
+        argument_types: argument_types.to_vec(),
+        overloads: overloads.iter().map(|overload| overload.describe_parameters()).collect(),
+    }.into()
+}
+
 fn decompress_function<'fn_name, 'fn_info>(function_name: &'fn_name str, args: Vec<FullValue>, function: &'fn_info FunctionInfo) -> Result<FullValue, Vec<SimpleError<'fn_name>>> {
     Ok(if function.can_inline_result && args.iter().all(|arg| arg.is_simple_value()) {
         let inlined_res = function.function.execute_iter(args.into_iter().map(|arg| Ok(arg.resolve_value_no_context())))
             .map_err(|runtime_error| vec![ASTBuildingError::CouldntInlineFunction { function_name, runtime_error }.into()])?;
-        FullValue::from(inlined_res)
+        FullValue::from(function.function.split_mutation(inlined_res).1)
     } else {
         FullValue::Function(ASTFunction { function: function.function.clone(), args })
     })
@@ -230,41 +529,72 @@ pub(crate) fn parse_property<'input>(idents: Pair<'input, Rule>, base: &Engine,
             };
             (type_of_var, value)
         })
-        .or_else(|| base.constants().get(variable.as_str())
+        .or_else(|| base.constant(variable.as_str())
             .map(|constant|
                 (constant.type_name.clone(), FullValue::from(constant.value.clone()))))
         .ok_or_else(|| vec![ASTBuildingError::VariableNotInScope { variable_name: variable.as_str() }.into()])?;
 
 
+    let mut receiver = context.find_variable(variable.as_str())
+        .map(|(block_level, var_index, _)| (block_level, var_index));
     let mut idents_and_params = idents.collect::<VecDeque<_>>();
+    let mut short_circuits_on_null = false;
     while !idents_and_params.is_empty() {
         let property = idents_and_params.pop_front().unwrap();
+        if property.as_rule() == Rule::optional_chaining {
+            short_circuits_on_null = true;
+            continue;
+        }
+        if short_circuits_on_null && value == FullValue::Null {
+            return Ok(FullValue::Null);
+        }
         let is_last_ident = idents_and_params.iter().all(|rule| rule.as_rule() != Rule::ident);
         let prepend = if !is_last_ident || prepend_on_last_property.is_none() { "get_" } else { prepend_on_last_property.unwrap() };
         let prepended = format!("{prepend}{}", property.as_str());
 
-        let function = base.find_function(type_name.clone(), None, &*prepended)
-            .or_else(|| base.find_function(type_name.clone(), None, property.as_str()))
-            .ok_or_else(|| vec![ASTBuildingError::PropertyFunctionNotFound {
-                preferred_property_to_find: prepended,
-                original_property: property.as_str(),
-                typename: type_name.clone(),
-            }.into()])?;
-        let mut args = vec![value];
+        let mut function_name = prepended.as_str();
+        let mut overloads = find_overloads(base, &context.used_modules, type_name.as_deref(), None, function_name)?;
+        if overloads.is_empty() {
+            function_name = property.as_str();
+            overloads = find_overloads(base, &context.used_modules, type_name.as_deref(), None, function_name)?;
+        }
+        let mut args = vec![(value, false)];
         if idents_and_params.front().as_ref().is_some_and(|rule| rule.as_rule() == Rule::property_params) {
-            for arg in idents_and_params.pop_front().unwrap().into_inner().map(|value| build_value_token(value, base, context)) {
-                args.push(arg?);
-            }
+            args.extend(build_spreadable_values(idents_and_params.pop_front().unwrap().into_inner(), base, context)?);
         }
         if is_last_ident && extra_value_for_last_property.is_some() {
-            args.push(mem::take(&mut extra_value_for_last_property).unwrap());
+            args.push((mem::take(&mut extra_value_for_last_property).unwrap(), false));
+        }
+        let mut argument_types = argument_types(&args, context);
+        if let Some(argument_types) = argument_types.as_mut() {
+            argument_types[0] = type_name.clone();
         }
+        let function = choose_overload(function_name, overloads, argument_types.as_deref(), context.effective_coercion_policy())?
+            .ok_or_else(|| vec![ASTBuildingError::PropertyFunctionNotFound {
+                preferred_property_to_find: prepended.clone(),
+                original_property: property.as_str(),
+                typename: type_name.clone(),
+            }.into()])?;
         type_name = function.return_type_name.clone();
+        let receiver = receiver.take();
+        let property_function = if short_circuits_on_null {
+            function.function.short_circuiting_on_null()
+        } else {
+            function.function.clone()
+        };
+        let (property_function, args) = if args.iter().any(|(_, is_spread)| *is_spread) {
+            (property_function.spreading_arguments(), vec![join_spread_values(args)?])
+        } else {
+            let mut args = args.into_iter().map(|(arg, _)| arg).collect::<Vec<_>>();
+            prepare_mutation_target(&function.function, &mut args, receiver, context);
+            (property_function, args)
+        };
         value = if function.can_inline_result && args.iter().all(|arg| arg.is_simple_value()) {
-            function.function.execute_iter(args.into_iter().map(|arg| Ok(arg.resolve_value_no_context())))
+            property_function.execute_iter(args.into_iter().map(|arg| Ok(arg.resolve_value_no_context())))
+                .map(|result| property_function.split_mutation(result).1)
                 .map_err(|err| vec![err.into()])?.into()
         } else {
-            FullValue::Function(ASTFunction { function: function.function.clone(), args })
+            FullValue::Function(ASTFunction { function: property_function, args })
         }
     }
     Ok(value)
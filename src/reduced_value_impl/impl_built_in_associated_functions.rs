@@ -0,0 +1,88 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::parsing::{FunctionDefinition, MoonValueKind};
+use crate::reduced_value_impl::impl_std_array;
+use crate::value::MoonValue;
+
+pub(crate) fn array_of(value: MoonValue, function_name: &str) -> Result<Vec<MoonValue>, String> {
+    match value {
+        MoonValue::Array(values) => Ok(values),
+        value => Err(format!("Function '{function_name}' can only be applied to arrays, while value is: {value}")),
+    }
+}
+
+fn map_of(value: MoonValue, function_name: &str) -> Result<Vec<(MoonValue, MoonValue)>, String> {
+    match value {
+        MoonValue::Map(entries) => Ok(entries),
+        value => Err(format!("Function '{function_name}' can only be applied to maps, while value is: {value}")),
+    }
+}
+
+pub(crate) fn get_built_in_associated_functions() -> Vec<FunctionDefinition> {
+    let string_functions = vec![
+        FunctionDefinition::new("len", |string: String| string.chars().count()),
+        FunctionDefinition::new("is_empty", |string: String| string.is_empty()),
+        FunctionDefinition::new("contains", |string: String, pattern: String| string.contains(&*pattern)),
+        FunctionDefinition::new("starts_with", |string: String, pattern: String| string.starts_with(&*pattern)),
+        FunctionDefinition::new("ends_with", |string: String, pattern: String| string.ends_with(&*pattern)),
+        FunctionDefinition::new("split", |string: String, separator: String| {
+            string.split(&*separator).map(|part| part.to_string()).collect::<Vec<_>>()
+        }),
+        FunctionDefinition::new("trim", |string: String| string.trim().to_string()),
+    ].into_iter().map(|function| function.associated_type_name(MoonValueKind::String));
+
+    let array_functions = vec![
+        FunctionDefinition::new("len", |array: MoonValue| array_of(array, "len").map(|array| array.len())),
+        FunctionDefinition::new("is_empty", |array: MoonValue| array_of(array, "is_empty").map(|array| array.is_empty())),
+        FunctionDefinition::new("contains", |array: MoonValue, value: MoonValue| {
+            array_of(array, "contains").map(|array| array.contains(&value))
+        }),
+        impl_std_array::push_function(),
+    ].into_iter().map(|function| function.associated_type_name(MoonValueKind::Array));
+
+    let map_functions = vec![
+        FunctionDefinition::new("len", |map: MoonValue| map_of(map, "len").map(|map| map.len())),
+        FunctionDefinition::new("is_empty", |map: MoonValue| map_of(map, "is_empty").map(|map| map.is_empty())),
+        FunctionDefinition::new("contains", |map: MoonValue, key: MoonValue| {
+            map_of(map, "contains").map(|map| map.iter().any(|(entry_key, _)| entry_key.eq(&key)))
+        }),
+        FunctionDefinition::new("get", |map: MoonValue, key: MoonValue| {
+            map_of(map, "get").map(|map| map.into_iter()
+                .find(|(entry_key, _)| entry_key.eq(&key))
+                .map(|(_, value)| value)
+                .unwrap_or(MoonValue::Null))
+        }),
+        FunctionDefinition::new("keys", |map: MoonValue| {
+            map_of(map, "keys").map(|map| MoonValue::Array(map.into_iter().map(|(key, _)| key).collect()))
+        }).known_return_type_name(MoonValueKind::Array),
+        FunctionDefinition::new("values", |map: MoonValue| {
+            map_of(map, "values").map(|map| MoonValue::Array(map.into_iter().map(|(_, value)| value).collect()))
+        }).known_return_type_name(MoonValueKind::Array),
+    ].into_iter().map(|function| function.associated_type_name(MoonValueKind::Map));
+
+    let integer_functions = vec![
+        FunctionDefinition::new("abs", |int: i128| int.checked_abs().ok_or_else(|| format!("The absolute value of {int} is too large"))),
+        FunctionDefinition::new("min", |int: i128, other: i128| int.min(other)),
+        FunctionDefinition::new("max", |int: i128, other: i128| int.max(other)),
+    ].into_iter().map(|function| function.associated_type_name(MoonValueKind::Integer));
+
+    let decimal_functions = vec![
+        FunctionDefinition::new("abs", |decimal: f64| decimal.abs()),
+        FunctionDefinition::new("min", |decimal: f64, other: f64| decimal.min(other)),
+        FunctionDefinition::new("max", |decimal: f64, other: f64| decimal.max(other)),
+        FunctionDefinition::new("round", |decimal: f64| libm::round(decimal)),
+        FunctionDefinition::new("floor", |decimal: f64| libm::floor(decimal)),
+        FunctionDefinition::new("ceil", |decimal: f64| libm::ceil(decimal)),
+    ].into_iter().map(|function| function.associated_type_name(MoonValueKind::Decimal));
+
+    string_functions
+        .chain(array_functions)
+        .chain(map_functions)
+        .chain(integer_functions)
+        .chain(decimal_functions)
+        .map(|function| function.inline())
+        .collect()
+}
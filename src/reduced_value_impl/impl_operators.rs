@@ -1,14 +1,100 @@
 use alloc::{format, vec};
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
 
+use crate::execution::RuntimeError;
+use crate::function::MoonFunction;
+use crate::reduced_value_impl::CoercionPolicy;
 use crate::value::MoonValue;
+use crate::MoonValueKind;
+
+/// Decides what happens when an operation between integers gives a result that doesn't fit in
+/// an integer, like `170141183460469231731687303715884105727 + 1`, it's chosen with
+/// [crate::Engine::with_overflow_policy].
+///
+/// Divisions and remainders by zero always fail with [ArithmeticError::DivisionByZero], whatever
+/// the policy is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OverflowPolicy {
+    /// Fails with [RuntimeError::Arithmetic].
+    #[default]
+    Checked,
+    /// Wraps around the boundary of the integer, so the maximum integer plus one is the minimum.
+    Wrapping,
+    /// Stays at the maximum or minimum integer, depending on the direction of the overflow.
+    Saturating,
+}
+
+/// Reason why an arithmetic operation failed, see [RuntimeError::Arithmetic].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArithmeticError {
+    /// The result of an operation between integers doesn't fit in an integer.
+    Overflow,
+    /// An integer was divided by zero, or the remainder of dividing by zero was asked.
+    DivisionByZero,
+    /// An integer was shifted by a negative amount of bits, or by more bits than it has.
+    InvalidShift,
+}
+
+impl Display for ArithmeticError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(match self {
+            ArithmeticError::Overflow => "the result is too large for an integer",
+            ArithmeticError::DivisionByZero => "it divides by zero",
+            ArithmeticError::InvalidShift => "the amount of bits to shift is out of range",
+        })
+    }
+}
+
+type UnaryOperator = fn(MoonValue, OverflowPolicy) -> Result<MoonValue, RuntimeError>;
+type BinaryOperator = fn(MoonValue, MoonValue, OverflowPolicy) -> Result<MoonValue, RuntimeError>;
+
+fn operator_error(function_error_message: String) -> RuntimeError {
+    RuntimeError::FunctionError { function_error_message }
+}
+
+/// Resolves an operation between integers following the overflow policy, 'checked' gives
+/// [None] on overflow, which is when 'wrapping' or 'saturating' are used instead.
+fn integer_operation(overflow_policy: OverflowPolicy, operation: impl FnOnce() -> String, checked: Option<i128>, wrapping: impl FnOnce() -> i128, saturating: impl FnOnce() -> i128) -> Result<MoonValue, RuntimeError> {
+    Ok(MoonValue::Integer(match (checked, overflow_policy) {
+        (Some(result), _) => result,
+        (None, OverflowPolicy::Checked) => return Err(RuntimeError::Arithmetic { operation: operation(), error: ArithmeticError::Overflow }),
+        (None, OverflowPolicy::Wrapping) => wrapping(),
+        (None, OverflowPolicy::Saturating) => saturating(),
+    }))
+}
+
+/// Fails with [ArithmeticError::DivisionByZero] if the divisor is zero.
+fn check_divisor(operator: &str, int_1: i128, int_2: i128) -> Result<(), RuntimeError> {
+    if int_2 == 0 {
+        return Err(RuntimeError::Arithmetic { operation: format!("{int_1} {operator} {int_2}"), error: ArithmeticError::DivisionByZero });
+    }
+    Ok(())
+}
+
+/// Shifts an integer, failing with [ArithmeticError::InvalidShift] if the amount of bits is
+/// negative or not smaller than the bits of an integer.
+fn shift(operator: &str, int_1: i128, int_2: i128, shift: fn(i128, u32) -> Option<i128>) -> Result<MoonValue, RuntimeError> {
+    u32::try_from(int_2).ok()
+        .and_then(|bits| shift(int_1, bits))
+        .map(MoonValue::Integer)
+        .ok_or_else(|| RuntimeError::Arithmetic { operation: format!("{int_1} {operator} {int_2}"), error: ArithmeticError::InvalidShift })
+}
+
+/// Turns the operands of a bitwise operator into integers, failing if a decimal doesn't fit in
+/// an integer.
+fn integer_operands(operator: &str, arg_1: MoonValue, arg_2: MoonValue) -> Result<(i128, i128), RuntimeError> {
+    let to_integer = |arg: MoonValue| i128::try_from(arg)
+        .map_err(|error| operator_error(format!("Operator '{operator}' can only be applied to decimals that fit in an integer, {error}")));
+    Ok((to_integer(arg_1)?, to_integer(arg_2)?))
+}
 
 const ARITHMETIC_RESULT_BOOL: u8 = 0;
 const ARITHMETIC_RESULT_INT: u8 = 1;
 const ARITHMETIC_RESULT_DECIMAL: u8 = 2;
 
-fn arithmetic_choice(arg1: MoonValue, arg2: MoonValue, on_bools: fn(bool, bool) -> Result<MoonValue, String>, on_int: fn(i128, i128) -> Result<MoonValue, String>, on_decimal: fn(f64, f64) -> Result<MoonValue, String>) -> Result<Result<MoonValue, String>, (MoonValue, MoonValue)> {
+fn arithmetic_choice(arg1: MoonValue, arg2: MoonValue, on_bools: impl FnOnce(bool, bool) -> Result<MoonValue, RuntimeError>, on_int: impl FnOnce(i128, i128) -> Result<MoonValue, RuntimeError>, on_decimal: impl FnOnce(f64, f64) -> Result<MoonValue, RuntimeError>) -> Result<Result<MoonValue, RuntimeError>, (MoonValue, MoonValue)> {
     match arithmetic_result(&arg1, &arg2) {
         None => Err((arg1, arg2)),
         Some(int) => {
@@ -38,31 +124,85 @@ fn arithmetic_result(arg1: &MoonValue, arg2: &MoonValue) -> Option<u8> {
     Some(if top_right_level >= top_left_level { top_right_level } else { top_left_level })
 }
 
-pub(crate) fn get_unary_operators() -> Vec<(&'static str, fn(MoonValue) -> Result<MoonValue, String>)> {
-    vec![
-        ("!", |arg| {
+/// Binary operators whose operands go through [arithmetic_choice].
+const PROMOTING_OPERATORS: [&str; 9] = ["+", "-", "*", "/", "%", ">", "<", ">=", "<="];
+
+/// Wraps a binary operator so it fails instead of promoting its operands into each other when the
+/// coercion policy doesn't allow it, operators that don't promote their operands are left as they
+/// are.
+pub(crate) fn with_operand_policy(operator: &str, function: MoonFunction, coercion_policy: CoercionPolicy) -> MoonFunction {
+    let Some(operator) = PROMOTING_OPERATORS.into_iter().find(|promoting_operator| *promoting_operator == operator) else {
+        return function;
+    };
+    if coercion_policy == CoercionPolicy::Lenient {
+        return function;
+    }
+    MoonFunction::from_fn_with_state(2, move |values, state| {
+        let arg_1 = values.next().ok_or(RuntimeError::AnArgumentIsMissing)??;
+        let arg_2 = values.next().ok_or(RuntimeError::AnArgumentIsMissing)??;
+        if !coercion_policy.allows_promotion(&arg_1, &arg_2) {
+            return Err(operator_error(format!("Operator '{operator}' cannot mix these values under the {coercion_policy:?} coercion policy, while args are:\narg1: {arg_1}\narg2: {arg_2}")));
+        }
+        function.execute_iter_with_state([Ok(arg_1), Ok(arg_2)].into_iter(), state)
+    })
+}
+
+/// Wraps an operator so it runs the first of its overloads for built-in types whose type is the
+/// kind of an operand, running the operator itself if none is, this is used when the types of
+/// the operands aren't known while parsing.
+pub(crate) fn dispatching_overloads(function: MoonFunction, overloads: Vec<(MoonValueKind<'static>, MoonFunction)>) -> MoonFunction {
+    MoonFunction::from_fn_with_state(function.number_of_params(), move |values, state| {
+        let values = values.collect::<Result<Vec<_>, _>>()?;
+        let overload = overloads.iter()
+            .find(|(kind, _)| values.iter().any(|value| CoercionPolicy::Strict.allows(kind, value)));
+        match overload {
+            Some((_, overload)) => overload.execute_iter_with_state(values.into_iter().map(Ok), state),
+            None => function.execute_iter_with_state(values.into_iter().map(Ok), state),
+        }
+    })
+}
+
+fn is_contained_in(value: MoonValue, collection: MoonValue, operator: &str) -> Result<bool, RuntimeError> {
+    match (value, collection) {
+        (value, MoonValue::Array(values)) => Ok(values.contains(&value)),
+        (key, MoonValue::Map(entries)) => Ok(entries.iter().any(|(entry_key, _)| entry_key.eq(&key))),
+        (MoonValue::String(substring), MoonValue::String(string)) => Ok(string.contains(&*substring)),
+        (value, collection) => Err(operator_error(format!("Operator '{operator}' can only be applied to arrays, maps, or strings when searching for a string, while args are:\narg1: {value}\narg2: {collection}"))),
+    }
+}
+
+pub(crate) fn get_unary_operators(overflow_policy: OverflowPolicy) -> Vec<(&'static str, MoonFunction)> {
+    let operators: Vec<(&'static str, UnaryOperator)> = vec![
+        ("!", |arg, _| {
             match arg {
                 MoonValue::Boolean(bool) => Ok(MoonValue::Boolean(!bool)),
                 MoonValue::Integer(int) => Ok(MoonValue::Integer(!int)),
-                MoonValue::Null | MoonValue::Decimal(_) | MoonValue::String(_) | MoonValue::Array(_) =>
-                    Err("Unary operator '!' only can be applied between booleans or integers".to_string()),
+                MoonValue::Null | MoonValue::Decimal(_) | MoonValue::String(_) | MoonValue::Array(_) | MoonValue::Map(_) =>
+                    Err(operator_error("Unary operator '!' only can be applied between booleans or integers".to_string())),
             }
         }),
-        ("-", |arg| {
+        ("-", |arg, overflow_policy| {
             match arg {
-                MoonValue::Integer(int) => Ok(MoonValue::Integer(-int)),
+                MoonValue::Integer(int) => integer_operation(overflow_policy, || format!("-{int}"),
+                                                             int.checked_neg(), || int.wrapping_neg(), || int.saturating_neg()),
                 MoonValue::Decimal(dec) => Ok(MoonValue::Decimal(-dec)),
-                MoonValue::Null | MoonValue::Boolean(_) | MoonValue::String(_) | MoonValue::Array(_) =>
-                    Err("Unary operator '-' only can be applied between integers or decimals".to_string()),
+                MoonValue::Null | MoonValue::Boolean(_) | MoonValue::String(_) | MoonValue::Array(_) | MoonValue::Map(_) =>
+                    Err(operator_error("Unary operator '-' only can be applied between integers or decimals".to_string())),
             }
         }),
-    ]
+    ];
+    operators.into_iter()
+        .map(|(name, operator)| (name, MoonFunction::from_fn(1, move |values| {
+            let arg = values.next().ok_or(RuntimeError::AnArgumentIsMissing)??;
+            operator(arg, overflow_policy)
+        })))
+        .collect()
 }
 
 
-pub(crate) fn get_binary_operators() -> Vec<(&'static str, fn(MoonValue, MoonValue) -> Result<MoonValue, String>)> {
-    vec![
-        ("+", |arg_1, arg_2| {
+pub(crate) fn get_binary_operators(overflow_policy: OverflowPolicy) -> Vec<(&'static str, MoonFunction)> {
+    let operators: Vec<(&'static str, BinaryOperator)> = vec![
+        ("+", |arg_1, arg_2, overflow_policy| {
             match (&arg_1, &arg_2) {
                 (MoonValue::String(string_1), MoonValue::String(string_2)) => {
                     return Ok(MoonValue::String(format!("{string_1}{string_2}")));
@@ -78,7 +218,8 @@ pub(crate) fn get_binary_operators() -> Vec<(&'static str, fn(MoonValue, MoonVal
 
             match arithmetic_choice(arg_1, arg_2,
                                     |bool_1, bool_2| Ok(MoonValue::Boolean(bool_1 || bool_2)),
-                                    |int_1, int_2| Ok(MoonValue::Integer(int_1.checked_add(int_2).unwrap_or(i128::MAX))),
+                                    |int_1, int_2| integer_operation(overflow_policy, || format!("{int_1} + {int_2}"),
+                                                                   int_1.checked_add(int_2), || int_1.wrapping_add(int_2), || int_1.saturating_add(int_2)),
                                     |dec_1, dec_2| Ok(MoonValue::Decimal(dec_1 + dec_2))) {
                 Ok(res) => { return res; }
                 Err((arg_1, arg_2)) => {
@@ -87,140 +228,164 @@ pub(crate) fn get_binary_operators() -> Vec<(&'static str, fn(MoonValue, MoonVal
                             array_1.extend(array_2.into_iter());
                             MoonValue::Array(array_1)
                         }
-                        (arg_1, arg_2) => return Err(format!("Operator '+' can only be applied between booleans, integers, decimals, arrays or strings, while args are:\narg1: {arg_1}\narg2: {arg_2}")),
+                        (arg_1, arg_2) => return Err(operator_error(format!("Operator '+' can only be applied between booleans, integers, decimals, arrays or strings, while args are:\narg1: {arg_1}\narg2: {arg_2}"))),
                     })
                 }
             }
         }),
-        ("-", |arg_1, arg_2| {
+        ("-", |arg_1, arg_2, overflow_policy| {
             arithmetic_choice(arg_1, arg_2,
                               |bool_1, bool_2| Ok(MoonValue::Boolean(bool_1 && !bool_2)),
-                              |int_1, int_2| Ok(MoonValue::Integer(int_1.checked_sub(int_2).unwrap_or(i128::MIN))),
+                              |int_1, int_2| integer_operation(overflow_policy, || format!("{int_1} - {int_2}"),
+                                                             int_1.checked_sub(int_2), || int_1.wrapping_sub(int_2), || int_1.saturating_sub(int_2)),
                               |dec_1, dec_2| Ok(MoonValue::Decimal(dec_1 - dec_2)))
-                .map_err(|(arg_1, arg_2)| format!("Operator '-' can only be applied between booleans, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))?
+                .map_err(|(arg_1, arg_2)| operator_error(format!("Operator '-' can only be applied between booleans, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")))?
         }),
-        ("*", |arg_1, arg_2| {
+        ("*", |arg_1, arg_2, overflow_policy| {
             arithmetic_choice(arg_1, arg_2,
                               |bool_1, bool_2| Ok(MoonValue::Boolean(bool_1 && bool_2)),
-                              |int_1, int_2| Ok(MoonValue::Integer(int_1.checked_mul(int_2).unwrap_or(i128::MAX))),
+                              |int_1, int_2| integer_operation(overflow_policy, || format!("{int_1} * {int_2}"),
+                                                             int_1.checked_mul(int_2), || int_1.wrapping_mul(int_2), || int_1.saturating_mul(int_2)),
                               |dec_1, dec_2| Ok(MoonValue::Decimal(dec_1 * dec_2)))
-                .map_err(|(arg_1, arg_2)| format!("Operator '*' can only be applied between booleans, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))?
+                .map_err(|(arg_1, arg_2)| operator_error(format!("Operator '*' can only be applied between booleans, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")))?
         }),
-        ("/", |arg_1, arg_2| {
+        ("/", |arg_1, arg_2, overflow_policy| {
             arithmetic_choice(arg_1, arg_2,
-                              |_, _| Err("Operator '/' cannot be applied between booleans".to_string()),
+                              |_, _| Err(operator_error("Operator '/' cannot be applied between booleans".to_string())),
                               |int_1, int_2| {
-                                  let res = (int_1 as f64) / (int_2 as f64);
-                                  if !res.is_normal() {
-                                      Ok(MoonValue::Integer(int_1.checked_div(int_2).unwrap_or(i128::MAX)))
-                                  } else if res == (res as i128 as f64) {
-                                      Ok(MoonValue::Integer(res as i128))
-                                  } else {
-                                      Ok(MoonValue::Decimal(res))
+                                  check_divisor("/", int_1, int_2)?;
+                                  match int_1.checked_rem(int_2) {
+                                      Some(remainder) if remainder != 0 => Ok(MoonValue::Decimal((int_1 as f64) / (int_2 as f64))),
+                                      _ => integer_operation(overflow_policy, || format!("{int_1} / {int_2}"),
+                                                             int_1.checked_div(int_2), || int_1.wrapping_div(int_2), || int_1.saturating_div(int_2)),
                                   }
                               },
                               |dec_1, dec_2| Ok(MoonValue::Decimal(dec_1 / dec_2)))
-                .map_err(|(arg_1, arg_2)| format!("Operator '/' can only be applied between integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))?
+                .map_err(|(arg_1, arg_2)| operator_error(format!("Operator '/' can only be applied between integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")))?
         }),
-        ("%", |arg_1, arg_2| {
+        ("%", |arg_1, arg_2, overflow_policy| {
             arithmetic_choice(arg_1, arg_2,
-                              |_, _| Err("Operator '%' cannot be applied between booleans".to_string()),
-                              |int_1, int_2| Ok(MoonValue::Integer(int_1.checked_rem(int_2).unwrap_or(0))),
+                              |_, _| Err(operator_error("Operator '%' cannot be applied between booleans".to_string())),
+                              |int_1, int_2| {
+                                  check_divisor("%", int_1, int_2)?;
+                                  integer_operation(overflow_policy, || format!("{int_1} % {int_2}"),
+                                                    int_1.checked_rem(int_2), || int_1.wrapping_rem(int_2), || int_1.wrapping_rem(int_2))
+                              },
                               |dec_1, dec_2| Ok(MoonValue::Decimal(dec_1 % dec_2)))
-                .map_err(|(arg_1, arg_2)| format!("Operator '%' can only be applied between integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))?
+                .map_err(|(arg_1, arg_2)| operator_error(format!("Operator '%' can only be applied between integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")))?
         }),
-        ("&&", |arg_1, arg_2| {
+        ("&&", |arg_1, arg_2, _| {
             Ok(match (arg_1, arg_2) {
                 (MoonValue::Boolean(bool_1), MoonValue::Boolean(bool_2)) => {
                     MoonValue::Boolean(bool_1 && bool_2)
                 }
                 args @ (MoonValue::Decimal(_) | MoonValue::Integer(_), MoonValue::Decimal(_) | MoonValue::Integer(_)) => {
-                    let int_1 = TryInto::<i128>::try_into(args.0).unwrap();
-                    let int_2 = TryInto::<i128>::try_into(args.1).unwrap();
+                    let (int_1, int_2) = integer_operands("&&", args.0, args.1)?;
                     MoonValue::Integer(int_1 & int_2)
                 }
-                (arg_1, arg_2) => return Err(format!("Operator '&&' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")),
+                (arg_1, arg_2) => return Err(operator_error(format!("Operator '&&' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))),
             })
         }),
-        ("||", |arg_1, arg_2| {
+        ("||", |arg_1, arg_2, _| {
             Ok(match (arg_1, arg_2) {
                 (MoonValue::Boolean(bool_1), MoonValue::Boolean(bool_2)) => {
                     MoonValue::Boolean(bool_1 || bool_2)
                 }
                 args @ (MoonValue::Decimal(_) | MoonValue::Integer(_), MoonValue::Decimal(_) | MoonValue::Integer(_)) => {
-                    let int_1 = TryInto::<i128>::try_into(args.0).unwrap();
-                    let int_2 = TryInto::<i128>::try_into(args.1).unwrap();
+                    let (int_1, int_2) = integer_operands("||", args.0, args.1)?;
                     MoonValue::Integer(int_1 | int_2)
                 }
-                (arg_1, arg_2) => return Err(format!("Operator '||' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")),
+                (arg_1, arg_2) => return Err(operator_error(format!("Operator '||' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))),
             })
         }),
-        ("^", |arg_1, arg_2| {
+        ("^", |arg_1, arg_2, _| {
             Ok(match (arg_1, arg_2) {
                 (MoonValue::Boolean(bool_1), MoonValue::Boolean(bool_2)) => {
                     MoonValue::Boolean(bool_1 ^ bool_2)
                 }
                 args @ (MoonValue::Decimal(_) | MoonValue::Integer(_), MoonValue::Decimal(_) | MoonValue::Integer(_)) => {
-                    let int_1 = TryInto::<i128>::try_into(args.0).unwrap();
-                    let int_2 = TryInto::<i128>::try_into(args.1).unwrap();
+                    let (int_1, int_2) = integer_operands("^", args.0, args.1)?;
                     MoonValue::Integer(int_1 ^ int_2)
                 }
-                (arg_1, arg_2) => return Err(format!("Operator '^' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")),
+                (arg_1, arg_2) => return Err(operator_error(format!("Operator '^' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))),
             })
         }),
-        ("<<", |arg_1, arg_2| {
-            Ok(match (arg_1, arg_2) {
+        ("<<", |arg_1, arg_2, _| {
+            match (arg_1, arg_2) {
                 args @ (MoonValue::Decimal(_) | MoonValue::Integer(_), MoonValue::Decimal(_) | MoonValue::Integer(_)) => {
-                    let int_1 = TryInto::<i128>::try_into(args.0).unwrap();
-                    let int_2 = TryInto::<i128>::try_into(args.1).unwrap();
-                    MoonValue::Integer(int_1 << int_2)
+                    let (int_1, int_2) = integer_operands("<<", args.0, args.1)?;
+                    shift("<<", int_1, int_2, i128::checked_shl)
                 }
-                (arg_1, arg_2) => return Err(format!("Operator '<<' can only be applied between integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")),
-            })
+                (arg_1, arg_2) => Err(operator_error(format!("Operator '<<' can only be applied between integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))),
+            }
         }),
-        (">>", |arg_1, arg_2| {
-            Ok(match (arg_1, arg_2) {
+        (">>", |arg_1, arg_2, _| {
+            match (arg_1, arg_2) {
                 args @ (MoonValue::Decimal(_) | MoonValue::Integer(_), MoonValue::Decimal(_) | MoonValue::Integer(_)) => {
-                    let int_1 = TryInto::<i128>::try_into(args.0).unwrap();
-                    let int_2 = TryInto::<i128>::try_into(args.1).unwrap();
-                    MoonValue::Integer(int_1 >> int_2)
+                    let (int_1, int_2) = integer_operands(">>", args.0, args.1)?;
+                    shift(">>", int_1, int_2, i128::checked_shr)
                 }
-                (arg_1, arg_2) => return Err(format!("Operator '>>' can only be applied between integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")),
-            })
+                (arg_1, arg_2) => Err(operator_error(format!("Operator '>>' can only be applied between integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))),
+            }
+        }),
+        ("in", |value, collection, _| {
+            is_contained_in(value, collection, "in").map(MoonValue::Boolean)
         }),
-        ("==", |arg_1, arg_2| {
+        ("not in", |value, collection, _| {
+            is_contained_in(value, collection, "not in").map(|is_contained| MoonValue::Boolean(!is_contained))
+        }),
+        ("==", |arg_1, arg_2, _| {
             Ok(MoonValue::Boolean(arg_1.eq(&arg_2)))
         }),
-        ("!=", |arg_1, arg_2| {
+        ("!=", |arg_1, arg_2, _| {
             Ok(MoonValue::Boolean(arg_1.ne(&arg_2)))
         }),
-        (">", |arg_1, arg_2| {
+        (">", |arg_1, arg_2, _| {
             arithmetic_choice(arg_1, arg_2,
                               |bool_1, bool_2| Ok(MoonValue::Boolean(bool_1 > bool_2)),
                               |int_1, int_2| Ok(MoonValue::Boolean(int_1 > int_2)),
                               |dec_1, dec_2| Ok(MoonValue::Boolean(dec_1 > dec_2)))
-                .map_err(|(arg_1, arg_2)| format!("Operator '>' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))?
+                .map_err(|(arg_1, arg_2)| operator_error(format!("Operator '>' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")))?
         }),
-        ("<", |arg_1, arg_2| {
+        ("<", |arg_1, arg_2, _| {
             arithmetic_choice(arg_1, arg_2,
                               |bool_1, bool_2| Ok(MoonValue::Boolean(bool_1 < bool_2)),
                               |int_1, int_2| Ok(MoonValue::Boolean(int_1 < int_2)),
                               |dec_1, dec_2| Ok(MoonValue::Boolean(dec_1 < dec_2)))
-                .map_err(|(arg_1, arg_2)| format!("Operator '<' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))?
+                .map_err(|(arg_1, arg_2)| operator_error(format!("Operator '<' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")))?
         }),
-        (">=", |arg_1, arg_2| {
+        (">=", |arg_1, arg_2, _| {
             arithmetic_choice(arg_1, arg_2,
                               |bool_1, bool_2| Ok(MoonValue::Boolean(bool_1 >= bool_2)),
                               |int_1, int_2| Ok(MoonValue::Boolean(int_1 >= int_2)),
                               |dec_1, dec_2| Ok(MoonValue::Boolean(dec_1 >= dec_2)))
-                .map_err(|(arg_1, arg_2)| format!("Operator '>=' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))?
+                .map_err(|(arg_1, arg_2)| operator_error(format!("Operator '>=' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")))?
         }),
-        ("<=", |arg_1, arg_2| {
+        ("<=", |arg_1, arg_2, _| {
             arithmetic_choice(arg_1, arg_2,
                               |bool_1, bool_2| Ok(MoonValue::Boolean(bool_1 <= bool_2)),
                               |int_1, int_2| Ok(MoonValue::Boolean(int_1 <= int_2)),
                               |dec_1, dec_2| Ok(MoonValue::Boolean(dec_1 <= dec_2)))
-                .map_err(|(arg_1, arg_2)| format!("Operator '<=' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}"))?
+                .map_err(|(arg_1, arg_2)| operator_error(format!("Operator '<=' can only be applied between boolean, integers or decimals, while args are:\narg1: {arg_1}\narg2: {arg_2}")))?
         }),
+    ];
+    operators.into_iter()
+        .map(|(name, operator)| (name, MoonFunction::from_fn(2, move |values| {
+            let arg_1 = values.next().ok_or(RuntimeError::AnArgumentIsMissing)??;
+            let arg_2 = values.next().ok_or(RuntimeError::AnArgumentIsMissing)??;
+            operator(arg_1, arg_2, overflow_policy)
+        })))
+        .collect()
+}
+
+pub(crate) fn get_lazy_binary_operators() -> Vec<(&'static str, MoonFunction)> {
+    vec![
+        ("??", MoonFunction::from_fn(2, |values| {
+            let value = values.next().ok_or(RuntimeError::AnArgumentIsMissing)??;
+            match value {
+                MoonValue::Null => values.next().ok_or(RuntimeError::AnArgumentIsMissing)?,
+                value => Ok(value),
+            }
+        })),
     ]
-}
\ No newline at end of file
+}
@@ -0,0 +1,570 @@
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use serde::de::value::{MapDeserializer, SeqDeserializer, StringDeserializer};
+use serde::de::{DeserializeOwned, DeserializeSeed, EnumAccess, IntoDeserializer, MapAccess, SeqAccess, VariantAccess, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant, SerializeTuple, SerializeTupleStruct, SerializeTupleVariant};
+use serde::{forward_to_deserialize_any, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::value::MoonValue;
+
+/// An error occurred when turning a Rust value into a [MoonValue] through [to_value] or when
+/// turning a [MoonValue] into a Rust value through [from_value].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SerdeError {
+    message: String,
+}
+
+impl SerdeError {
+    /// Message describing why the conversion failed.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for SerdeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl core::error::Error for SerdeError {}
+
+impl serde::ser::Error for SerdeError {
+    fn custom<T: Display>(message: T) -> Self {
+        Self { message: message.to_string() }
+    }
+}
+
+impl serde::de::Error for SerdeError {
+    fn custom<T: Display>(message: T) -> Self {
+        Self { message: message.to_string() }
+    }
+}
+
+/// Turns a [MoonValue] into any type implementing [serde::Deserialize], like the result of a
+/// script into a struct.
+///
+/// Maps are read as structs by the names of their fields, null values are read as `None` and
+/// enums are read either as the string of their name for unit variants, like `"Easy"`, or as a
+/// map with a single entry going from the name to the content, like `{"Custom": 7}`.
+///
+/// ```rust
+/// use moon_script::{from_value, ContextBuilder, Engine};
+/// #[derive(serde::Deserialize, Debug, PartialEq)]
+/// struct Settings {
+///     volume: u8,
+///     name: String,
+///     scale: Option<f64>,
+/// }
+/// let result = Engine::new().parse(r###"
+///     {"volume": 80, "name": "moon", "scale": null}
+/// "###, ContextBuilder::default()).unwrap().execute().unwrap();
+/// let settings: Settings = from_value(result).unwrap();
+/// assert_eq!(Settings { volume: 80, name: "moon".to_string(), scale: None }, settings);
+/// ```
+pub fn from_value<T: DeserializeOwned>(value: MoonValue) -> Result<T, SerdeError> {
+    T::deserialize(value)
+}
+
+/// Turns any type implementing [serde::Serialize] into a [MoonValue], so it can be given to a
+/// script, this is the opposite of [from_value].
+///
+/// Structs and maps become maps, sequences and tuples become arrays, `None` and `()` become
+/// null, unit enum variants become the string of their name and any other enum variant becomes a
+/// map with a single entry going from the name to the content.
+///
+/// ```rust
+/// use moon_script::{to_value, MoonValue};
+/// #[derive(serde::Serialize)]
+/// struct Player {
+///     name: &'static str,
+///     position: (i32, i32),
+/// }
+/// let value = to_value(&Player { name: "moon", position: (3, 4) }).unwrap();
+/// assert_eq!(MoonValue::Map(vec![
+///     (MoonValue::from("name"), MoonValue::from("moon")),
+///     (MoonValue::from("position"), MoonValue::from([3, 4])),
+/// ]), value);
+/// ```
+pub fn to_value<T: Serialize + ?Sized>(value: &T) -> Result<MoonValue, SerdeError> {
+    value.serialize(ValueSerializer)
+}
+
+impl Serialize for MoonValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            MoonValue::Null => serializer.serialize_unit(),
+            MoonValue::Boolean(bool) => serializer.serialize_bool(*bool),
+            MoonValue::Integer(int) => match i64::try_from(*int) {
+                Ok(int) => serializer.serialize_i64(int),
+                Err(_) => serializer.serialize_i128(*int),
+            },
+            MoonValue::Decimal(decimal) => serializer.serialize_f64(*decimal),
+            MoonValue::String(string) => serializer.serialize_str(string),
+            MoonValue::Array(values) => {
+                let mut sequence = serializer.serialize_seq(Some(values.len()))?;
+                for value in values {
+                    sequence.serialize_element(value)?;
+                }
+                sequence.end()
+            }
+            MoonValue::Map(entries) => {
+                let mut map = serializer.serialize_map(Some(entries.len()))?;
+                for (key, value) in entries {
+                    map.serialize_entry(key, value)?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for MoonValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(MoonValueVisitor)
+    }
+}
+
+struct MoonValueVisitor;
+
+impl<'de> Visitor<'de> for MoonValueVisitor {
+    type Value = MoonValue;
+
+    fn expecting(&self, formatter: &mut Formatter) -> core::fmt::Result {
+        formatter.write_str("a value representable by a MoonValue")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, value: bool) -> Result<Self::Value, E> {
+        Ok(MoonValue::Boolean(value))
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Self::Value, E> {
+        Ok(MoonValue::Integer(value as i128))
+    }
+
+    fn visit_i128<E: serde::de::Error>(self, value: i128) -> Result<Self::Value, E> {
+        Ok(MoonValue::Integer(value))
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Self::Value, E> {
+        Ok(MoonValue::Integer(value as i128))
+    }
+
+    fn visit_u128<E: serde::de::Error>(self, value: u128) -> Result<Self::Value, E> {
+        i128::try_from(value)
+            .map(MoonValue::Integer)
+            .map_err(|_| E::custom(alloc::format!("The integer {value} doesn't fit in a MoonValue")))
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, value: f64) -> Result<Self::Value, E> {
+        Ok(MoonValue::Decimal(value))
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(MoonValue::String(value.to_string()))
+    }
+
+    fn visit_string<E: serde::de::Error>(self, value: String) -> Result<Self::Value, E> {
+        Ok(MoonValue::String(value))
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        Ok(MoonValue::from(value.to_vec()))
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(MoonValue::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        MoonValue::deserialize(deserializer)
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<Self::Value, E> {
+        Ok(MoonValue::Null)
+    }
+
+    fn visit_newtype_struct<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        MoonValue::deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut sequence: A) -> Result<Self::Value, A::Error> {
+        let mut values = Vec::with_capacity(sequence.size_hint().unwrap_or_default());
+        while let Some(value) = sequence.next_element()? {
+            values.push(value);
+        }
+        Ok(MoonValue::Array(values))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entries = Vec::with_capacity(map.size_hint().unwrap_or_default());
+        while let Some(entry) = map.next_entry()? {
+            entries.push(entry);
+        }
+        Ok(MoonValue::Map(entries))
+    }
+}
+
+impl<'de> IntoDeserializer<'de, SerdeError> for MoonValue {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self::Deserializer {
+        self
+    }
+}
+
+impl<'de> Deserializer<'de> for MoonValue {
+    type Error = SerdeError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            MoonValue::Null => visitor.visit_unit(),
+            MoonValue::Boolean(bool) => visitor.visit_bool(bool),
+            MoonValue::Integer(int) => match i64::try_from(int) {
+                Ok(int) => visitor.visit_i64(int),
+                Err(_) => match u64::try_from(int) {
+                    Ok(int) => visitor.visit_u64(int),
+                    Err(_) => visitor.visit_i128(int),
+                },
+            },
+            MoonValue::Decimal(decimal) => visitor.visit_f64(decimal),
+            MoonValue::String(string) => visitor.visit_string(string),
+            MoonValue::Array(values) => {
+                let mut sequence = SeqDeserializer::new(values.into_iter());
+                let value = visitor.visit_seq(&mut sequence)?;
+                sequence.end()?;
+                Ok(value)
+            }
+            MoonValue::Map(entries) => {
+                let mut map = MapDeserializer::new(entries.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            MoonValue::Null => visitor.visit_none(),
+            value => visitor.visit_some(value),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            MoonValue::String(variant) => visitor.visit_enum(StringDeserializer::new(variant)),
+            MoonValue::Map(mut entries) if entries.len() == 1 => {
+                let (variant, value) = entries.remove(0);
+                visitor.visit_enum(EnumValue { variant, value })
+            }
+            value => Err(serde::de::Error::custom(alloc::format!(
+                "Enum {name} must be a string or a map with a single entry, but it is: {value}"))),
+        }
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+        bytes byte_buf unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+/// Enum variant read from a map with a single entry, going from the name of the variant to its
+/// content.
+struct EnumValue {
+    variant: MoonValue,
+    value: MoonValue,
+}
+
+impl<'de> EnumAccess<'de> for EnumValue {
+    type Error = SerdeError;
+    type Variant = MoonValue;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, Self::Variant), Self::Error> {
+        Ok((seed.deserialize(self.variant)?, self.value))
+    }
+}
+
+impl<'de> VariantAccess<'de> for MoonValue {
+    type Error = SerdeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_map(visitor)
+    }
+}
+
+/// Wraps the content of an enum variant into a map with a single entry going from the name of
+/// the variant to its content, the variant is None when the value isn't an enum variant.
+fn with_variant(variant: Option<&'static str>, value: MoonValue) -> MoonValue {
+    match variant {
+        Some(variant) => MoonValue::Map(vec![(MoonValue::from(variant), value)]),
+        None => value,
+    }
+}
+
+struct ValueSerializer;
+
+impl Serializer for ValueSerializer {
+    type Ok = MoonValue;
+    type Error = SerdeError;
+    type SerializeSeq = ArraySerializer;
+    type SerializeTuple = ArraySerializer;
+    type SerializeTupleStruct = ArraySerializer;
+    type SerializeTupleVariant = ArraySerializer;
+    type SerializeMap = MapSerializer;
+    type SerializeStruct = MapSerializer;
+    type SerializeStructVariant = MapSerializer;
+
+    fn serialize_bool(self, value: bool) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::Boolean(value))
+    }
+
+    fn serialize_i8(self, value: i8) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value))
+    }
+
+    fn serialize_i16(self, value: i16) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value))
+    }
+
+    fn serialize_i32(self, value: i32) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value))
+    }
+
+    fn serialize_i64(self, value: i64) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value))
+    }
+
+    fn serialize_i128(self, value: i128) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::Integer(value))
+    }
+
+    fn serialize_u8(self, value: u8) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value))
+    }
+
+    fn serialize_u16(self, value: u16) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value))
+    }
+
+    fn serialize_u32(self, value: u32) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value))
+    }
+
+    fn serialize_u64(self, value: u64) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value))
+    }
+
+    fn serialize_u128(self, value: u128) -> Result<MoonValue, SerdeError> {
+        i128::try_from(value)
+            .map(MoonValue::Integer)
+            .map_err(|_| serde::ser::Error::custom(alloc::format!("The integer {value} doesn't fit in a MoonValue")))
+    }
+
+    fn serialize_f32(self, value: f32) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value))
+    }
+
+    fn serialize_f64(self, value: f64) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::Decimal(value))
+    }
+
+    fn serialize_char(self, value: char) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::String(value.to_string()))
+    }
+
+    fn serialize_str(self, value: &str) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value))
+    }
+
+    fn serialize_bytes(self, value: &[u8]) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(value.to_vec()))
+    }
+
+    fn serialize_none(self) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::Null)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<MoonValue, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::Null)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::Null)
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<MoonValue, SerdeError> {
+        Ok(MoonValue::from(variant))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<MoonValue, SerdeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &T) -> Result<MoonValue, SerdeError> {
+        Ok(with_variant(Some(variant), value.serialize(self)?))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ArraySerializer, SerdeError> {
+        Ok(ArraySerializer { values: Vec::with_capacity(len.unwrap_or_default()), variant: None })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ArraySerializer, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<ArraySerializer, SerdeError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, len: usize) -> Result<ArraySerializer, SerdeError> {
+        Ok(ArraySerializer { values: Vec::with_capacity(len), variant: Some(variant) })
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<MapSerializer, SerdeError> {
+        Ok(MapSerializer { entries: Vec::with_capacity(len.unwrap_or_default()), next_key: None, variant: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<MapSerializer, SerdeError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str, len: usize) -> Result<MapSerializer, SerdeError> {
+        Ok(MapSerializer { entries: Vec::with_capacity(len), next_key: None, variant: Some(variant) })
+    }
+}
+
+struct ArraySerializer {
+    values: Vec<MoonValue>,
+    variant: Option<&'static str>,
+}
+
+impl SerializeSeq for ArraySerializer {
+    type Ok = MoonValue;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        self.values.push(value.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn end(self) -> Result<MoonValue, SerdeError> {
+        Ok(with_variant(self.variant, MoonValue::Array(self.values)))
+    }
+}
+
+impl SerializeTuple for ArraySerializer {
+    type Ok = MoonValue;
+    type Error = SerdeError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<MoonValue, SerdeError> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleStruct for ArraySerializer {
+    type Ok = MoonValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<MoonValue, SerdeError> {
+        SerializeSeq::end(self)
+    }
+}
+
+impl SerializeTupleVariant for ArraySerializer {
+    type Ok = MoonValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<MoonValue, SerdeError> {
+        SerializeSeq::end(self)
+    }
+}
+
+struct MapSerializer {
+    entries: Vec<(MoonValue, MoonValue)>,
+    next_key: Option<MoonValue>,
+    variant: Option<&'static str>,
+}
+
+impl SerializeMap for MapSerializer {
+    type Ok = MoonValue;
+    type Error = SerdeError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), SerdeError> {
+        self.next_key = Some(key.serialize(ValueSerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), SerdeError> {
+        let key = self.next_key.take()
+            .ok_or_else(|| serde::ser::Error::custom("A map value was serialized before its key"))?;
+        self.entries.push((key, value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<MoonValue, SerdeError> {
+        Ok(with_variant(self.variant, MoonValue::Map(self.entries)))
+    }
+}
+
+impl SerializeStruct for MapSerializer {
+    type Ok = MoonValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        self.entries.push((MoonValue::from(key), value.serialize(ValueSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<MoonValue, SerdeError> {
+        SerializeMap::end(self)
+    }
+}
+
+impl SerializeStructVariant for MapSerializer {
+    type Ok = MoonValue;
+    type Error = SerdeError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), SerdeError> {
+        SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<MoonValue, SerdeError> {
+        SerializeMap::end(self)
+    }
+}
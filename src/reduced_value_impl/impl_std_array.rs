@@ -0,0 +1,150 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::Ordering;
+
+use crate::parsing::{FunctionDefinition, MoonValueKind};
+use crate::reduced_value_impl::impl_built_in_associated_functions::array_of;
+use crate::value::MoonValue;
+
+/// Name of the module these functions are registered in, so they are called like `array/len(x)`.
+pub(crate) const ARRAY_MODULE_NAME: &str = "array";
+
+/// Return value of the functions that mutate their array, holding both the mutated array and the
+/// result of the function.
+fn mutated(array: Vec<MoonValue>, result: MoonValue) -> MoonValue {
+    MoonValue::Array(vec![MoonValue::Array(array), result])
+}
+
+fn compare(value: &MoonValue, other: &MoonValue) -> Option<Ordering> {
+    match (value, other) {
+        (MoonValue::Integer(value), MoonValue::Integer(other)) => Some(value.cmp(other)),
+        (MoonValue::Integer(value), MoonValue::Decimal(other)) => (*value as f64).partial_cmp(other),
+        (MoonValue::Decimal(value), MoonValue::Integer(other)) => value.partial_cmp(&(*other as f64)),
+        (MoonValue::Decimal(value), MoonValue::Decimal(other)) => value.partial_cmp(other),
+        (MoonValue::String(value), MoonValue::String(other)) => Some(value.cmp(other)),
+        (MoonValue::Boolean(value), MoonValue::Boolean(other)) => Some(value.cmp(other)),
+        _ => None,
+    }
+}
+
+fn index_in_bounds(index: usize, length: usize, function_name: &str) -> Result<usize, String> {
+    if index < length {
+        Ok(index)
+    } else {
+        Err(format!("Function '{function_name}' received index {index}, which is out of bounds for array of length {length}"))
+    }
+}
+
+/// Function `push` of the `array` module, which is also a default method of arrays, so
+/// `values.push(5)` works without [crate::Engine::with_std_array].
+pub(crate) fn push_function() -> FunctionDefinition {
+    FunctionDefinition::new("push", |array: MoonValue, value: MoonValue| {
+        array_of(array, "push").map(|mut array| {
+            array.push(value);
+            mutated(array, MoonValue::Null)
+        })
+    }).mutating()
+}
+
+/// Functions of the `array` module, the ones that mutate their array write it back into the
+/// variable it was read from, so `array/push(values, 5)` and `values.push(5)` both modify
+/// `values`.
+pub(crate) fn get_std_array_functions() -> Vec<FunctionDefinition> {
+    let mutating_functions = vec![
+        push_function(),
+        FunctionDefinition::new("pop", |array: MoonValue| {
+            array_of(array, "pop").map(|mut array| {
+                let popped_value = array.pop().unwrap_or(MoonValue::Null);
+                mutated(array, popped_value)
+            })
+        }),
+        FunctionDefinition::new("insert", |array: MoonValue, index: usize, value: MoonValue| {
+            let mut array = array_of(array, "insert")?;
+            index_in_bounds(index, array.len() + 1, "insert")?;
+            array.insert(index, value);
+            Ok::<_, String>(mutated(array, MoonValue::Null))
+        }),
+        FunctionDefinition::new("remove", |array: MoonValue, index: usize| {
+            let mut array = array_of(array, "remove")?;
+            let removed_value = array.remove(index_in_bounds(index, array.len(), "remove")?);
+            Ok::<_, String>(mutated(array, removed_value))
+        }),
+        FunctionDefinition::new("reverse", |array: MoonValue| {
+            array_of(array, "reverse").map(|mut array| {
+                array.reverse();
+                mutated(array, MoonValue::Null)
+            })
+        }),
+        FunctionDefinition::new("sort", |array: MoonValue| {
+            let mut array = array_of(array, "sort")?;
+            let mut incomparable_values = None;
+            array.sort_by(|value, other| compare(value, other).unwrap_or_else(|| {
+                incomparable_values.get_or_insert_with(|| (value.clone(), other.clone()));
+                Ordering::Equal
+            }));
+            match incomparable_values {
+                Some((value, other)) => Err(format!("Function 'sort' cannot compare values {value} and {other}")),
+                None => Ok(mutated(array, MoonValue::Null)),
+            }
+        }),
+    ].into_iter().map(|function| function.mutating());
+
+    let functions = vec![
+        FunctionDefinition::new("len", |array: MoonValue| array_of(array, "len").map(|array| array.len())),
+        FunctionDefinition::new("contains", |array: MoonValue, value: MoonValue| {
+            array_of(array, "contains").map(|array| array.contains(&value))
+        }),
+        FunctionDefinition::new("index_of", |array: MoonValue, value: MoonValue| {
+            array_of(array, "index_of").map(|array| array.iter().position(|element| element.eq(&value)))
+        }),
+        FunctionDefinition::new("concat", |array: MoonValue, other: MoonValue| {
+            let mut array = array_of(array, "concat")?;
+            array.extend(array_of(other, "concat")?);
+            Ok::<_, String>(MoonValue::Array(array))
+        }).known_return_type_name(MoonValueKind::Array),
+        FunctionDefinition::new("slice", |array: MoonValue, start: usize, end: usize| {
+            let array = array_of(array, "slice")?;
+            if start > end || end > array.len() {
+                return Err(format!("Cannot slice from {start} to {end} an array of length {}", array.len()));
+            }
+            Ok(MoonValue::Array(array[start..end].to_vec()))
+        }).known_return_type_name(MoonValueKind::Array),
+        FunctionDefinition::new("sum", |array: MoonValue| {
+            let array = array_of(array, "sum")?;
+            if array.iter().all(|value| matches!(value, MoonValue::Integer(_))) {
+                return array.into_iter()
+                    .try_fold(0_i128, |sum, value| match value {
+                        MoonValue::Integer(int) => sum.checked_add(int),
+                        _ => unreachable!(),
+                    })
+                    .map(MoonValue::Integer)
+                    .ok_or_else(|| "Function 'sum' overflowed".into());
+            }
+            array.into_iter()
+                .try_fold(0.0, |sum, value| match value {
+                    MoonValue::Integer(int) => Ok(sum + int as f64),
+                    MoonValue::Decimal(decimal) => Ok(sum + decimal),
+                    value => Err(format!("Function 'sum' can only add numbers, while value is: {value}")),
+                })
+                .map(MoonValue::Decimal)
+        }),
+        FunctionDefinition::new("unique", |array: MoonValue| {
+            array_of(array, "unique").map(|array| {
+                let mut unique_values = Vec::with_capacity(array.len());
+                for value in array {
+                    if !unique_values.contains(&value) {
+                        unique_values.push(value);
+                    }
+                }
+                MoonValue::Array(unique_values)
+            })
+        }).known_return_type_name(MoonValueKind::Array),
+    ];
+
+    mutating_functions
+        .chain(functions)
+        .map(|function| function.inline())
+        .collect()
+}
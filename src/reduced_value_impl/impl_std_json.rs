@@ -0,0 +1,383 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter, Write};
+use core::str::FromStr;
+
+use crate::parsing::{FunctionDefinition, MoonValueKind};
+use crate::value::MoonValue;
+
+/// Name of the module these functions are registered in, so they are called like
+/// `json/encode(x)`.
+const JSON_MODULE_NAME: &str = "json";
+
+/// How many arrays and objects can be nested inside each other when reading JSON, reading is
+/// recursive, so without a limit a deeply nested text would overflow the stack.
+pub const MAX_JSON_DEPTH: usize = 128;
+
+/// An error occurred when turning a [MoonValue] into JSON or when reading JSON into a
+/// [MoonValue].
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonError {
+    /// A decimal was NaN or infinite, which JSON has no way to represent.
+    NonFiniteDecimal { value: f64 },
+    /// The text isn't valid JSON, 'position' is the byte offset where the problem was found.
+    InvalidJson { reason: &'static str, position: usize },
+    /// The text nests more than [MAX_JSON_DEPTH] arrays and objects, 'position' is the byte
+    /// offset of the array or object that went over the limit.
+    TooDeeplyNested { position: usize },
+}
+
+impl Display for JsonError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            JsonError::NonFiniteDecimal { value } =>
+                write!(f, "The decimal {value} cannot be represented in JSON"),
+            JsonError::InvalidJson { reason, position } =>
+                write!(f, "Invalid JSON at byte {position}: {reason}"),
+            JsonError::TooDeeplyNested { position } =>
+                write!(f, "Invalid JSON at byte {position}: More than {MAX_JSON_DEPTH} arrays and objects are nested"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for JsonError {}
+
+impl MoonValue {
+    /// Turns this value into JSON text, the values are mapped as:
+    ///
+    /// - Null, booleans, strings and arrays are mapped to their JSON counterpart.
+    /// - Integers are written with all their digits, even those beyond ±2^53, so readers that
+    ///   store numbers as doubles (like JavaScript's `JSON.parse`) might lose precision on them.
+    /// - Decimals are written with the shortest text that reads back as the same value, always
+    ///   including a fraction or an exponent (`3.0`, `1e300`), so [MoonValue::from_json] reads them
+    ///   back as decimals, NaN and infinite decimals fail with [JsonError::NonFiniteDecimal].
+    /// - Maps are written as objects, keeping the order of their entries, as JSON keys must be
+    ///   strings, string keys are written as they are and any other key is written as the text of
+    ///   its JSON, so the key `1` becomes `"1"` and the key `[1, 2]` becomes `"[1,2]"`.
+    ///
+    /// ```rust
+    /// use moon_script::MoonValue;
+    /// let value = MoonValue::Map(vec![
+    ///     (MoonValue::from("hp"), MoonValue::from(7)),
+    ///     (MoonValue::from(1), MoonValue::from([0.5, 2.0])),
+    /// ]);
+    /// assert_eq!(r#"{"hp":7,"1":[0.5,2.0]}"#, value.to_json().unwrap());
+    /// ```
+    pub fn to_json(&self) -> Result<String, JsonError> {
+        let mut json = String::new();
+        write_json(self, &mut json)?;
+        Ok(json)
+    }
+
+    /// Reads JSON text into a value, the values are mapped as:
+    ///
+    /// - Null, booleans, strings and arrays are mapped to their MoonValue counterpart.
+    /// - Numbers without fraction nor exponent are read as integers, unless they don't fit in an
+    ///   i128, in which case they are read as the closest decimal.
+    /// - Numbers with a fraction or an exponent are read as decimals, even if they are whole
+    ///   numbers like `3.0`.
+    /// - Objects are read as maps with string keys, keeping the order of their entries, including
+    ///   repeated keys.
+    ///
+    /// Texts nesting more than [MAX_JSON_DEPTH] arrays and objects fail with
+    /// [JsonError::TooDeeplyNested].
+    ///
+    /// ```rust
+    /// use moon_script::MoonValue;
+    /// let value = MoonValue::from_json(r#"{"hp": 7, "position": [0.5, 2e1], "name": null}"#).unwrap();
+    /// assert_eq!(MoonValue::Map(vec![
+    ///     (MoonValue::from("hp"), MoonValue::from(7)),
+    ///     (MoonValue::from("position"), MoonValue::from([0.5, 20.0])),
+    ///     (MoonValue::from("name"), MoonValue::Null),
+    /// ]), value);
+    /// ```
+    pub fn from_json(json: &str) -> Result<MoonValue, JsonError> {
+        let mut reader = JsonReader { json, position: 0, depth: 0 };
+        let value = reader.read_value()?;
+        reader.skip_white_space();
+        if reader.position < json.len() {
+            return Err(reader.error("Unexpected text after the value"));
+        }
+        Ok(value)
+    }
+}
+
+fn write_json(value: &MoonValue, json: &mut String) -> Result<(), JsonError> {
+    match value {
+        MoonValue::Null => json.push_str("null"),
+        MoonValue::Boolean(bool) => json.push_str(if *bool { "true" } else { "false" }),
+        MoonValue::Integer(int) => json.push_str(&int.to_string()),
+        MoonValue::Decimal(decimal) if decimal.is_finite() => { let _ = write!(json, "{decimal:?}"); }
+        MoonValue::Decimal(decimal) => return Err(JsonError::NonFiniteDecimal { value: *decimal }),
+        MoonValue::String(string) => write_json_string(string, json),
+        MoonValue::Array(values) => {
+            json.push('[');
+            for (index, value) in values.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                write_json(value, json)?;
+            }
+            json.push(']');
+        }
+        MoonValue::Map(entries) => {
+            json.push('{');
+            for (index, (key, value)) in entries.iter().enumerate() {
+                if index > 0 {
+                    json.push(',');
+                }
+                match key {
+                    MoonValue::String(key) => write_json_string(key, json),
+                    key => write_json_string(&key.to_json()?, json),
+                }
+                json.push(':');
+                write_json(value, json)?;
+            }
+            json.push('}');
+        }
+    }
+    Ok(())
+}
+
+fn write_json_string(string: &str, json: &mut String) {
+    json.push('"');
+    for char in string.chars() {
+        match char {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            '\u{08}' => json.push_str("\\b"),
+            '\u{0C}' => json.push_str("\\f"),
+            char if char < ' ' => { let _ = write!(json, "\\u{:04x}", char as u32); }
+            char => json.push(char),
+        }
+    }
+    json.push('"');
+}
+
+struct JsonReader<'input> {
+    json: &'input str,
+    position: usize,
+    depth: usize,
+}
+
+impl<'input> JsonReader<'input> {
+    fn error(&self, reason: &'static str) -> JsonError {
+        JsonError::InvalidJson { reason, position: self.position }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.json.as_bytes().get(self.position).copied()
+    }
+
+    fn skip_white_space(&mut self) {
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.position += 1;
+        }
+    }
+
+    fn expect(&mut self, text: &'static str, reason: &'static str) -> Result<(), JsonError> {
+        if self.json[self.position..].starts_with(text) {
+            self.position += text.len();
+            Ok(())
+        } else {
+            Err(self.error(reason))
+        }
+    }
+
+    fn read_value(&mut self) -> Result<MoonValue, JsonError> {
+        self.skip_white_space();
+        match self.peek() {
+            Some(b'n') => self.expect("null", "Expected null").map(|_| MoonValue::Null),
+            Some(b't') => self.expect("true", "Expected true").map(|_| MoonValue::Boolean(true)),
+            Some(b'f') => self.expect("false", "Expected false").map(|_| MoonValue::Boolean(false)),
+            Some(b'"') => self.read_string().map(MoonValue::String),
+            Some(b'[') => self.read_nested(Self::read_array),
+            Some(b'{') => self.read_nested(Self::read_object),
+            Some(b'-' | b'0'..=b'9') => self.read_number(),
+            Some(_) => Err(self.error("Expected a value")),
+            None => Err(self.error("Unexpected end of the text")),
+        }
+    }
+
+    fn read_nested(&mut self, read: fn(&mut Self) -> Result<MoonValue, JsonError>) -> Result<MoonValue, JsonError> {
+        if self.depth == MAX_JSON_DEPTH {
+            return Err(JsonError::TooDeeplyNested { position: self.position });
+        }
+        self.depth += 1;
+        let value = read(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn read_array(&mut self) -> Result<MoonValue, JsonError> {
+        self.position += 1;
+        let mut values = Vec::new();
+        self.skip_white_space();
+        if self.peek() == Some(b']') {
+            self.position += 1;
+            return Ok(MoonValue::Array(values));
+        }
+        loop {
+            values.push(self.read_value()?);
+            self.skip_white_space();
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b']') => {
+                    self.position += 1;
+                    return Ok(MoonValue::Array(values));
+                }
+                _ => return Err(self.error("Expected ',' or ']'")),
+            }
+        }
+    }
+
+    fn read_object(&mut self) -> Result<MoonValue, JsonError> {
+        self.position += 1;
+        let mut entries = Vec::new();
+        self.skip_white_space();
+        if self.peek() == Some(b'}') {
+            self.position += 1;
+            return Ok(MoonValue::Map(entries));
+        }
+        loop {
+            self.skip_white_space();
+            if self.peek() != Some(b'"') {
+                return Err(self.error("Expected a string key"));
+            }
+            let key = self.read_string()?;
+            self.skip_white_space();
+            self.expect(":", "Expected ':'")?;
+            entries.push((MoonValue::String(key), self.read_value()?));
+            self.skip_white_space();
+            match self.peek() {
+                Some(b',') => self.position += 1,
+                Some(b'}') => {
+                    self.position += 1;
+                    return Ok(MoonValue::Map(entries));
+                }
+                _ => return Err(self.error("Expected ',' or '}'")),
+            }
+        }
+    }
+
+    fn read_number(&mut self) -> Result<MoonValue, JsonError> {
+        let start = self.position;
+        if self.peek() == Some(b'-') {
+            self.position += 1;
+        }
+        match self.peek() {
+            Some(b'0') => self.position += 1,
+            Some(b'1'..=b'9') => self.skip_digits(),
+            _ => return Err(self.error("Expected a digit")),
+        }
+        let mut is_integer = true;
+        if self.peek() == Some(b'.') {
+            is_integer = false;
+            self.position += 1;
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("Expected a digit after the decimal point"));
+            }
+            self.skip_digits();
+        }
+        if matches!(self.peek(), Some(b'e' | b'E')) {
+            is_integer = false;
+            self.position += 1;
+            if matches!(self.peek(), Some(b'+' | b'-')) {
+                self.position += 1;
+            }
+            if !matches!(self.peek(), Some(b'0'..=b'9')) {
+                return Err(self.error("Expected a digit in the exponent"));
+            }
+            self.skip_digits();
+        }
+        let number = &self.json[start..self.position];
+        if is_integer {
+            if let Ok(int) = i128::from_str(number) {
+                return Ok(MoonValue::Integer(int));
+            }
+        }
+        f64::from_str(number).map(MoonValue::Decimal).map_err(|_| self.error("Invalid number"))
+    }
+
+    fn skip_digits(&mut self) {
+        while matches!(self.peek(), Some(b'0'..=b'9')) {
+            self.position += 1;
+        }
+    }
+
+    fn read_string(&mut self) -> Result<String, JsonError> {
+        self.position += 1;
+        let mut string = String::new();
+        loop {
+            let rest = &self.json[self.position..];
+            let Some(end) = rest.find(['"', '\\']) else {
+                return Err(self.error("Unterminated string"));
+            };
+            if rest[..end].chars().any(|char| char < ' ') {
+                return Err(self.error("Control characters must be escaped in strings"));
+            }
+            string.push_str(&rest[..end]);
+            self.position += end + 1;
+            if rest.as_bytes()[end] == b'"' {
+                return Ok(string);
+            }
+            let escaped = match self.peek() {
+                Some(b'"') => '"',
+                Some(b'\\') => '\\',
+                Some(b'/') => '/',
+                Some(b'b') => '\u{08}',
+                Some(b'f') => '\u{0C}',
+                Some(b'n') => '\n',
+                Some(b'r') => '\r',
+                Some(b't') => '\t',
+                Some(b'u') => {
+                    self.position += 1;
+                    let code = self.read_hex_code()?;
+                    let code = if (0xD800..0xDC00).contains(&code) {
+                        self.expect("\\u", "Expected the low surrogate of a surrogate pair")?;
+                        let low_code = self.read_hex_code()?;
+                        if !(0xDC00..0xE000).contains(&low_code) {
+                            return Err(self.error("Invalid low surrogate"));
+                        }
+                        0x10000 + ((code - 0xD800) << 10) + (low_code - 0xDC00)
+                    } else {
+                        code
+                    };
+                    string.push(char::from_u32(code).ok_or_else(|| self.error("Invalid unicode escape"))?);
+                    continue;
+                }
+                _ => return Err(self.error("Invalid escape")),
+            };
+            string.push(escaped);
+            self.position += 1;
+        }
+    }
+
+    fn read_hex_code(&mut self) -> Result<u32, JsonError> {
+        let code = self.json.get(self.position..self.position + 4)
+            .filter(|code| code.bytes().all(|byte| byte.is_ascii_hexdigit()))
+            .and_then(|code| u32::from_str_radix(code, 16).ok())
+            .ok_or_else(|| self.error("Expected four hexadecimal digits"))?;
+        self.position += 4;
+        Ok(code)
+    }
+}
+
+/// Functions of the `json` module, these are inlined when their arguments are constant.
+pub(crate) fn get_std_json_functions() -> Vec<FunctionDefinition> {
+    vec![
+        FunctionDefinition::new("encode", |value: MoonValue| value.to_json().map_err(|error| error.to_string()))
+            .known_return_type_name(MoonValueKind::String),
+        FunctionDefinition::new("decode", |json: String| {
+            MoonValue::from_json(&json).map_err(|error| format!("Could not decode JSON: {error}"))
+        }),
+    ].into_iter()
+        .map(|function| function.module_name(JSON_MODULE_NAME).inline())
+        .collect()
+}
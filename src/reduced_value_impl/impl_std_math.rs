@@ -0,0 +1,90 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::engine::Constant;
+use crate::parsing::{FunctionDefinition, MoonValueKind};
+use crate::value::MoonValue;
+
+/// Name of the module these functions are registered in, so they are called like `math/sqrt(x)`.
+const MATH_MODULE_NAME: &str = "math";
+
+fn decimal_of(value: MoonValue, function_name: &str) -> Result<f64, String> {
+    match value {
+        MoonValue::Integer(int) => Ok(int as f64),
+        MoonValue::Decimal(decimal) => Ok(decimal),
+        value => Err(format!("Function '{function_name}' can only be applied to numbers, while value is: {value}")),
+    }
+}
+
+/// Applies the integer operation if every value is an integer, or the decimal one otherwise, so
+/// the kind of number is kept when it's possible.
+fn on_numbers<const N: usize>(values: [MoonValue; N], function_name: &str, integer_operation: fn([i128; N]) -> Option<i128>,
+                              decimal_operation: fn([f64; N]) -> f64) -> Result<MoonValue, String> {
+    if values.iter().all(|value| matches!(value, MoonValue::Integer(_))) {
+        let integers = values.map(|value| match value {
+            MoonValue::Integer(int) => int,
+            _ => unreachable!(),
+        });
+        return integer_operation(integers)
+            .map(MoonValue::Integer)
+            .ok_or_else(|| format!("Function '{function_name}' overflowed with arguments {integers:?}"));
+    }
+    let mut decimals = [0.0; N];
+    for (decimal, value) in decimals.iter_mut().zip(values) {
+        *decimal = decimal_of(value, function_name)?;
+    }
+    Ok(MoonValue::Decimal(decimal_operation(decimals)))
+}
+
+pub(crate) fn get_std_math_functions() -> Vec<FunctionDefinition> {
+    vec![
+        FunctionDefinition::new("abs", |value: MoonValue| {
+            on_numbers([value], "abs", |[int]| int.checked_abs(), |[decimal]| libm::fabs(decimal))
+        }),
+        FunctionDefinition::new("min", |value: MoonValue, other: MoonValue| {
+            on_numbers([value, other], "min", |[int, other]| Some(int.min(other)), |[decimal, other]| libm::fmin(decimal, other))
+        }),
+        FunctionDefinition::new("max", |value: MoonValue, other: MoonValue| {
+            on_numbers([value, other], "max", |[int, other]| Some(int.max(other)), |[decimal, other]| libm::fmax(decimal, other))
+        }),
+        FunctionDefinition::new("clamp", |value: MoonValue, min: MoonValue, max: MoonValue| {
+            on_numbers([value, min, max], "clamp",
+                       |[int, min, max]| Some(int.max(min).min(max)),
+                       |[decimal, min, max]| libm::fmin(libm::fmax(decimal, min), max))
+        }),
+        FunctionDefinition::new("pow", |base: MoonValue, exponent: MoonValue| {
+            match (base, exponent) {
+                (MoonValue::Integer(base), MoonValue::Integer(exponent)) if exponent >= 0 => u32::try_from(exponent).ok()
+                    .and_then(|exponent| base.checked_pow(exponent))
+                    .map(MoonValue::Integer)
+                    .ok_or_else(|| format!("Function 'pow' overflowed with arguments {base} and {exponent}")),
+                (base, exponent) => Ok(MoonValue::Decimal(libm::pow(decimal_of(base, "pow")?, decimal_of(exponent, "pow")?))),
+            }
+        }),
+        FunctionDefinition::new("floor", |decimal: f64| libm::floor(decimal)),
+        FunctionDefinition::new("ceil", |decimal: f64| libm::ceil(decimal)),
+        FunctionDefinition::new("round", |decimal: f64| libm::round(decimal)),
+        FunctionDefinition::new("sqrt", |decimal: f64| libm::sqrt(decimal)),
+        FunctionDefinition::new("sin", |decimal: f64| libm::sin(decimal)),
+        FunctionDefinition::new("cos", |decimal: f64| libm::cos(decimal)),
+        FunctionDefinition::new("atan2", |y: f64, x: f64| libm::atan2(y, x)),
+        FunctionDefinition::new("lerp", |from: f64, to: f64, progress: f64| from + (to - from) * progress),
+        FunctionDefinition::new("e", || core::f64::consts::E),
+    ].into_iter()
+        .map(|function| function.module_name(MATH_MODULE_NAME).inline())
+        .collect()
+}
+
+/// Constants of the `math` module, these are global as constants can't belong to a module, so
+/// Euler's number is given by `math/e()` instead, as a global `E` would easily collide with the
+/// constants and variables of the host.
+pub(crate) fn get_std_math_constants() -> Vec<(&'static str, Constant)> {
+    [
+        ("PI", core::f64::consts::PI),
+        ("TAU", core::f64::consts::TAU),
+    ].into_iter()
+        .map(|(name, value)| (name, Constant::new(value).associated_type(MoonValueKind::Decimal)))
+        .collect()
+}
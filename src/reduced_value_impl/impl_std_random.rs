@@ -0,0 +1,95 @@
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::parsing::{FunctionDefinition, MoonValueKind};
+use crate::reduced_value_impl::impl_built_in_associated_functions::array_of;
+use crate::value::MoonValue;
+
+/// Name of the module these functions are registered in, so they are called like `random/int(1, 6)`.
+const RANDOM_MODULE_NAME: &str = "random";
+
+/// State used when the executor wasn't given a seed.
+pub(crate) const DEFAULT_SEED: u64 = 0x853C_49E6_748F_EA9B;
+
+/// SplitMix64 generator, it's small, fast and any seed gives a good sequence.
+struct RandomState {
+    state: u64,
+}
+
+impl RandomState {
+    fn from_value(value: MoonValue) -> Self {
+        let state = match value {
+            MoonValue::Integer(state) => state as u64,
+            _ => DEFAULT_SEED,
+        };
+        Self { state }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut value = self.state;
+        value = (value ^ (value >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        value = (value ^ (value >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        value ^ (value >> 31)
+    }
+
+    fn next_below(&mut self, bound: u128) -> u128 {
+        let value = ((self.next_u64() as u128) << 64) | self.next_u64() as u128;
+        value % bound
+    }
+
+    fn next_decimal(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64
+    }
+
+    /// Return value of the random functions, holding both the new state and the result, so the
+    /// state is written back into the execution.
+    fn with_result(self, result: MoonValue) -> MoonValue {
+        MoonValue::Array(vec![MoonValue::Integer(self.state as i128), result])
+    }
+}
+
+/// Functions of the `random` module, these receive the state of the execution's random number
+/// generator, so they must never be inlined, as that would freeze their values into the AST.
+pub(crate) fn get_std_random_functions() -> Vec<FunctionDefinition> {
+    vec![
+        FunctionDefinition::new("int", |state: MoonValue, min: i128, max: i128| {
+            if min > max {
+                return Err(format!("Function 'int' received a minimum {min} greater than its maximum {max}"));
+            }
+            let mut state = RandomState::from_value(state);
+            let value = match max.abs_diff(min).checked_add(1) {
+                Some(span) => min.wrapping_add(state.next_below(span) as i128),
+                None => (((state.next_u64() as u128) << 64) | state.next_u64() as u128) as i128,
+            };
+            Ok::<_, String>(state.with_result(MoonValue::Integer(value)))
+        }),
+        FunctionDefinition::new("decimal", |state: MoonValue| {
+            let mut state = RandomState::from_value(state);
+            let value = state.next_decimal();
+            state.with_result(MoonValue::Decimal(value))
+        }),
+        FunctionDefinition::new("choice", |state: MoonValue, array: MoonValue| {
+            let mut array = array_of(array, "choice")?;
+            let mut state = RandomState::from_value(state);
+            let value = if array.is_empty() {
+                MoonValue::Null
+            } else {
+                array.swap_remove(state.next_below(array.len() as u128) as usize)
+            };
+            Ok::<_, String>(state.with_result(value))
+        }),
+        FunctionDefinition::new("shuffle", |state: MoonValue, array: MoonValue| {
+            let mut array = array_of(array, "shuffle")?;
+            let mut state = RandomState::from_value(state);
+            for index in (1..array.len()).rev() {
+                array.swap(index, state.next_below(index as u128 + 1) as usize);
+            }
+            Ok::<_, String>(state.with_result(MoonValue::Array(array)))
+        }).known_return_type_name(MoonValueKind::Array),
+    ].into_iter()
+        .map(|function| function.module_name(RANDOM_MODULE_NAME).receiving_random_state())
+        .collect()
+}
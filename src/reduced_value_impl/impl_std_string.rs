@@ -0,0 +1,69 @@
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::parsing::FunctionDefinition;
+use crate::reduced_value_impl::impl_built_in_associated_functions::array_of;
+use crate::value::MoonValue;
+
+/// Name of the module these functions are registered in, so they are called like `string/len(x)`.
+const STRING_MODULE_NAME: &str = "string";
+
+/// Turns a value into text, strings are returned as they are instead of being quoted.
+fn text_of(value: MoonValue) -> String {
+    match value {
+        MoonValue::String(string) => string,
+        value => value.to_string(),
+    }
+}
+
+pub(crate) fn get_std_string_functions() -> Vec<FunctionDefinition> {
+    vec![
+        FunctionDefinition::new("len", |string: String| string.chars().count()),
+        FunctionDefinition::new("upper", |string: String| string.to_uppercase()),
+        FunctionDefinition::new("lower", |string: String| string.to_lowercase()),
+        FunctionDefinition::new("split", |string: String, separator: String| {
+            string.split(separator.as_str()).map(|part| part.to_string()).collect::<Vec<_>>()
+        }),
+        FunctionDefinition::new("join", |values: MoonValue, separator: String| {
+            array_of(values, "join").map(|values| values.into_iter().map(text_of).collect::<Vec<_>>().join(&separator))
+        }),
+        FunctionDefinition::new("replace", |string: String, from: String, to: String| string.replace(from.as_str(), &to)),
+        FunctionDefinition::new("starts_with", |string: String, prefix: String| string.starts_with(prefix.as_str())),
+        FunctionDefinition::new("find", |string: String, pattern: String| {
+            string.find(pattern.as_str()).map(|byte_index| string[..byte_index].chars().count())
+        }),
+        FunctionDefinition::new("substring", |string: String, start: usize, end: usize| {
+            let length = string.chars().count();
+            if start > end || end > length {
+                return Err(format!("Cannot take the substring from {start} to {end} of a string of length {length}"));
+            }
+            Ok(string.chars().skip(start).take(end - start).collect::<String>())
+        }),
+        FunctionDefinition::new("repeat", |string: String, times: usize| string.repeat(times)),
+        FunctionDefinition::new("pad_left", |string: String, width: usize, padding: String| {
+            let mut padding_chars = padding.chars();
+            match (padding_chars.next(), padding_chars.next()) {
+                (Some(padding), None) => {
+                    let missing_chars = width.saturating_sub(string.chars().count());
+                    Ok(core::iter::repeat_n(padding, missing_chars).chain(string.chars()).collect::<String>())
+                }
+                _ => Err(format!("The padding must be a single character, but it is '{padding}'")),
+            }
+        }),
+        FunctionDefinition::new("format", |template: String, values: MoonValue| {
+            let mut values = array_of(values, "format")?.into_iter();
+            let mut parts = template.split("{}");
+            let mut result = parts.next().unwrap_or_default().to_string();
+            for part in parts {
+                let value = values.next().ok_or_else(|| format!("The template '{template}' has more placeholders than values"))?;
+                result.push_str(&text_of(value));
+                result.push_str(part);
+            }
+            Ok::<_, String>(result)
+        }),
+    ].into_iter()
+        .map(|function| function.module_name(STRING_MODULE_NAME).inline())
+        .collect()
+}
@@ -1,11 +1,25 @@
+use alloc::collections::BTreeMap;
+use alloc::format;
 use alloc::string::{String, ToString};
 use alloc::vec;
 use alloc::vec::Vec;
+use core::convert::Infallible;
+use core::fmt::{Display, Formatter};
 use core::str::FromStr;
 
+use crate::parsing::MoonValueKind;
 use crate::value::{FullValue, MoonValue};
 
 pub(crate) mod impl_operators;
+pub(crate) mod impl_built_in_associated_functions;
+#[cfg(feature = "serde")]
+pub(crate) mod impl_serde;
+pub(crate) mod impl_std_array;
+#[cfg(feature = "json")]
+pub(crate) mod impl_std_json;
+pub(crate) mod impl_std_math;
+pub(crate) mod impl_std_random;
+pub(crate) mod impl_std_string;
 
 impl From<MoonValue> for FullValue {
     fn from(value: MoonValue) -> Self {
@@ -20,6 +34,11 @@ impl From<MoonValue> for FullValue {
                     .map(|reduced_value| Self::from(reduced_value))
                     .collect()
             ),
+            MoonValue::Map(entries) => FullValue::Map(
+                entries.into_iter()
+                    .map(|(key, value)| (Self::from(key), Self::from(value)))
+                    .collect()
+            ),
         }
     }
 }
@@ -31,8 +50,174 @@ impl From<MoonValue> for () {
     }
 }
 
+/// A [MoonValue] couldn't be turned into a Rust value because it isn't of the expected kind.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConversionError {
+    /// Kind of value that was expected.
+    pub expected: MoonValueKind<'static>,
+    /// Value that was found instead.
+    pub found: MoonValue,
+    /// Where the value was found inside of the converted value, like `[2]` for the third element
+    /// of an array or `["hp"]` for the value of the key "hp" of a map, this is empty if it's the
+    /// converted value itself.
+    pub path: String,
+}
+
+impl ConversionError {
+    /// Creates an error for a value that was expected to be of another kind.
+    pub fn new(expected: MoonValueKind<'static>, found: MoonValue) -> Self {
+        Self { expected, found, path: String::new() }
+    }
+
+    /// Marks this error as happening at the given index of an array.
+    pub fn at_index(mut self, index: usize) -> Self {
+        self.path.insert_str(0, &format!("[{index}]"));
+        self
+    }
+
+    /// Marks this error as happening at the value of the given key of a map.
+    pub fn at_key(mut self, key: &MoonValue) -> Self {
+        self.path.insert_str(0, &format!("[{key}]"));
+        self
+    }
+
+    /// Marks this error as happening at the given field of a struct.
+    pub fn at_field(mut self, field: &str) -> Self {
+        self.path.insert_str(0, &format!(".{field}"));
+        self
+    }
+}
+
+impl Display for ConversionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let expected = self.expected.get_moon_value_type().unwrap_or("valid value");
+        if self.path.is_empty() {
+            write!(f, "expected a {expected}, but found {}", self.found)
+        } else {
+            write!(f, "expected a {expected} at {}, but found {}", self.path, self.found)
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ConversionError {}
+
+impl From<Infallible> for ConversionError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+/// Allows types whose conversion fails with `()` to be used as arguments of functions, as `()`
+/// tells nothing about the failure, the error expects a [MoonValueKind::Invalid] and found a
+/// [MoonValue::Null].
+impl From<()> for ConversionError {
+    fn from(_: ()) -> Self {
+        Self::new(MoonValueKind::Invalid, MoonValue::Null)
+    }
+}
+
+/// Decides which conversions between kinds of values are done silently, applying to the arguments
+/// given to Rust functions, to the predicates of `if`, `while` and `assert`, and to the operands of
+/// the arithmetic and comparison operators, which otherwise promote booleans into integers and
+/// integers into decimals.
+///
+/// It's chosen with [crate::Engine::with_coercion_policy], and can be overridden for a single script
+/// with [crate::ContextBuilder::with_coercion_policy].
+///
+/// ```rust
+/// use moon_script::{CoercionPolicy, ContextBuilder, Engine, FunctionDefinition};
+///
+/// let mut engine = Engine::new().with_coercion_policy(CoercionPolicy::Strict);
+/// engine.add_function(FunctionDefinition::new("double", |value: i64| value * 2));
+/// assert!(engine.parse("double(\"2\")", ContextBuilder::new()).unwrap().executor().execute().is_err());
+/// assert!(engine.parse("if 1 { return 1; }", ContextBuilder::new()).is_err());
+///
+/// let context = ContextBuilder::new().with_coercion_policy(CoercionPolicy::Lenient);
+/// let result: i64 = engine.parse("double(\"2\")", context).unwrap()
+///     .executor().execute().unwrap().try_into().unwrap();
+/// assert_eq!(4, result);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CoercionPolicy {
+    /// Values must already be of the expected kind, so an `i64` argument only accepts integers,
+    /// predicates must be booleans, and operators can't mix booleans, integers and decimals.
+    ///
+    /// Only the kind of the value itself is checked, the elements of arrays and maps are converted
+    /// as usual, as are the arguments of parameters taking a [MoonValue] or a custom type.
+    Strict,
+    /// Like [CoercionPolicy::Strict], but integers and decimals can be used in place of each other,
+    /// as long as decimals given where an integer is expected don't have a fractional part.
+    NumericOnly,
+    /// Every conversion the `TryFrom<MoonValue>` implementations know of is done, like parsing
+    /// strings into numbers, truncating decimals or taking the first element of an array.
+    #[default]
+    Lenient,
+}
+
+impl CoercionPolicy {
+    /// Tells if a value can be given where a value of the expected kind is wanted.
+    pub(crate) fn allows(self, expected: &MoonValueKind, value: &MoonValue) -> bool {
+        match (self, expected, value) {
+            (CoercionPolicy::Lenient, _, _)
+            | (_, MoonValueKind::Null, MoonValue::Null)
+            | (_, MoonValueKind::Boolean, MoonValue::Boolean(_))
+            | (_, MoonValueKind::Integer, MoonValue::Integer(_))
+            | (_, MoonValueKind::Decimal, MoonValue::Decimal(_))
+            | (_, MoonValueKind::String, MoonValue::String(_))
+            | (_, MoonValueKind::Array, MoonValue::Array(_))
+            | (_, MoonValueKind::Map, MoonValue::Map(_))
+            | (CoercionPolicy::NumericOnly, MoonValueKind::Decimal, MoonValue::Integer(_)) => true,
+            (CoercionPolicy::NumericOnly, MoonValueKind::Integer, MoonValue::Decimal(decimal)) =>
+                *decimal == *decimal as i128 as f64,
+            _ => false,
+        }
+    }
+
+    /// Tells if a value of the kind `found` can be given where one of the kind `expected` is, this
+    /// is how overloads are told apart while parsing, so decimals aren't given where integers are
+    /// expected, as it depends on the value.
+    pub(crate) fn allows_kind(self, expected: &MoonValueKind, found: &MoonValueKind) -> bool {
+        expected == found || match self {
+            CoercionPolicy::Lenient => true,
+            CoercionPolicy::NumericOnly => matches!((expected, found), (MoonValueKind::Decimal, MoonValueKind::Integer)),
+            CoercionPolicy::Strict => false,
+        }
+    }
+
+    /// Policy used for telling overloads apart, as any value could be given to any overload under
+    /// a lenient policy, in which case only numbers are coerced.
+    pub(crate) fn for_overloads(self) -> CoercionPolicy {
+        if self == CoercionPolicy::Lenient { CoercionPolicy::NumericOnly } else { self }
+    }
+
+    /// Tells if an operator can promote one of these operands into the kind of the other one,
+    /// operands that aren't booleans, integers or decimals are left for the operator to decide.
+    pub(crate) fn allows_promotion(self, lhs: &MoonValue, rhs: &MoonValue) -> bool {
+        match (self, lhs, rhs) {
+            (CoercionPolicy::Lenient, _, _)
+            | (_, MoonValue::Boolean(_), MoonValue::Boolean(_))
+            | (_, MoonValue::Integer(_), MoonValue::Integer(_))
+            | (_, MoonValue::Decimal(_), MoonValue::Decimal(_))
+            | (CoercionPolicy::NumericOnly, MoonValue::Integer(_) | MoonValue::Decimal(_), MoonValue::Integer(_) | MoonValue::Decimal(_)) => true,
+            (_, MoonValue::Boolean(_) | MoonValue::Integer(_) | MoonValue::Decimal(_), MoonValue::Boolean(_) | MoonValue::Integer(_) | MoonValue::Decimal(_)) => false,
+            _ => true,
+        }
+    }
+
+    /// Turns the value a predicate resolved to into a boolean, giving the value back if the policy
+    /// doesn't allow it to be used as one.
+    pub(crate) fn predicate_to_bool(self, value: MoonValue) -> Result<bool, MoonValue> {
+        match (self, value) {
+            (_, MoonValue::Boolean(boolean)) => Ok(boolean),
+            (CoercionPolicy::Lenient, value) => bool::try_from(value).map_err(|error| error.found),
+            (_, value) => Err(value),
+        }
+    }
+}
+
 impl TryFrom<MoonValue> for bool {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: MoonValue) -> Result<Self, Self::Error> {
         Ok(match value {
@@ -40,57 +225,90 @@ impl TryFrom<MoonValue> for bool {
             MoonValue::Integer(int) => int >= 1,
             MoonValue::Decimal(decimal) => decimal >= 1.0,
             MoonValue::String(string) => {
-                if string.eq("true") || string.eq("no") {
+                if string.eq("true") || string.eq("yes") {
                     true
                 } else if string.eq("false") || string.eq("no") {
                     false
                 } else {
-                    return i128::from_str(&string).ok().map(|n| n > 1)
+                    return i128::from_str(&string).ok().map(|n| n >= 1)
                         .or_else(|| f64::from_str(&string).ok().map(|decimal| decimal >= 1.0))
-                        .ok_or(());
+                        .ok_or_else(|| ConversionError::new(MoonValueKind::Boolean, MoonValue::String(string)));
                 }
             }
-            _ => return Err(()),
+            other => return Err(ConversionError::new(MoonValueKind::Boolean, other)),
         })
     }
 }
 
 impl TryFrom<MoonValue> for String {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: MoonValue) -> Result<Self, Self::Error> {
         match value {
             MoonValue::String(string) => Ok(string),
-            _ => Err(())
+            other => Err(ConversionError::new(MoonValueKind::String, other))
         }
     }
 }
 
-impl<T: TryFrom<MoonValue>> TryFrom<MoonValue> for Vec<T> where T::Error: Default {
-    type Error = T::Error;
+impl<T: TryFrom<MoonValue>> TryFrom<MoonValue> for Vec<T> where T::Error: Into<ConversionError> {
+    type Error = ConversionError;
 
     fn try_from(value: MoonValue) -> Result<Self, Self::Error> {
         Ok(match value {
             MoonValue::Null => Vec::new(),
             MoonValue::Array(values) => {
                 let mut res = Vec::with_capacity(values.len());
-                for value in values.into_iter() {
-                    res.push(T::try_from(value)?);
+                for (index, value) in values.into_iter().enumerate() {
+                    res.push(T::try_from(value).map_err(|error| error.into().at_index(index))?);
                 }
                 res
             }
-            other => vec![T::try_from(other)?]
+            other => vec![T::try_from(other).map_err(Into::into)?]
         })
     }
 }
 
+/// Turns the entries of a map into Rust keys and values, used by the conversions of maps.
+fn try_from_entries<K: TryFrom<MoonValue>, V: TryFrom<MoonValue>, Map: FromIterator<(K, V)>>(value: MoonValue) -> Result<Map, ConversionError>
+where K::Error: Into<ConversionError>, V::Error: Into<ConversionError> {
+    match value {
+        MoonValue::Map(entries) => entries.into_iter()
+            .map(|(key, value)| {
+                let converted_value = V::try_from(value).map_err(|error| error.into().at_key(&key))?;
+                Ok((K::try_from(key).map_err(Into::into)?, converted_value))
+            })
+            .collect(),
+        other => Err(ConversionError::new(MoonValueKind::Map, other)),
+    }
+}
+
+impl<K: TryFrom<MoonValue> + Ord, V: TryFrom<MoonValue>> TryFrom<MoonValue> for BTreeMap<K, V>
+where K::Error: Into<ConversionError>, V::Error: Into<ConversionError> {
+    type Error = ConversionError;
+
+    fn try_from(value: MoonValue) -> Result<Self, Self::Error> {
+        try_from_entries(value)
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: TryFrom<MoonValue> + Eq + core::hash::Hash, V: TryFrom<MoonValue>> TryFrom<MoonValue> for std::collections::HashMap<K, V>
+where K::Error: Into<ConversionError>, V::Error: Into<ConversionError> {
+    type Error = ConversionError;
+
+    fn try_from(value: MoonValue) -> Result<Self, Self::Error> {
+        try_from_entries(value)
+    }
+}
+
 impl TryFrom<MoonValue> for vec::IntoIter<MoonValue> {
-    type Error = ();
+    type Error = ConversionError;
 
     fn try_from(value: MoonValue) -> Result<Self, Self::Error> {
         Ok(match value {
             MoonValue::Array(values) => values.into_iter(),
-            _ => return Err(()),
+            other => return Err(ConversionError::new(MoonValueKind::Array, other)),
         })
     }
 }
@@ -99,20 +317,29 @@ impl TryFrom<MoonValue> for vec::IntoIter<MoonValue> {
 
 
 
-macro_rules! impl_try_from_for_reduced_value {
+macro_rules! impl_try_from_for_integer {
     ($($type:ty),+) => {
         $(
             impl TryFrom<MoonValue> for $type{
-                type Error = ();
+                type Error = ConversionError;
 
                 fn try_from(value: MoonValue) -> Result<Self, Self::Error> {
+                    // Values past the bounds of this type are rejected instead of being truncated,
+                    // the upper bound is built from a power of two so it's exact as a decimal.
+                    let upper_bound = ((<$type>::MAX / 2 + 1) as f64) * 2.0;
                     Ok(match value {
                         MoonValue::Boolean(bool) => (if bool {1}else{0}) as $type,
-                        MoonValue::Integer(int) => int as $type,
-                        MoonValue::Decimal(decimal) => decimal as $type,
-                        MoonValue::Array(array) => return Self::try_from(array.get(0).ok_or(())?.clone()).map_err(|_|()),
-                        MoonValue::String(string)=><$type>::from_str(&string).map_err(|_|())?,
-                        _ => return Err(()),
+                        MoonValue::Integer(int) => match <$type>::try_from(int) {
+                            Ok(value) => value,
+                            Err(_) => return Err(ConversionError::new(MoonValueKind::Integer, MoonValue::Integer(int))),
+                        },
+                        MoonValue::Decimal(decimal) if libm::trunc(decimal) >= <$type>::MIN as f64 && libm::trunc(decimal) < upper_bound => decimal as $type,
+                        MoonValue::Array(array) if !array.is_empty() => return Self::try_from(array.into_iter().next().unwrap()),
+                        MoonValue::String(string) => match <$type>::from_str(&string) {
+                            Ok(value) => value,
+                            Err(_) => return Err(ConversionError::new(MoonValueKind::Integer, MoonValue::String(string))),
+                        },
+                        other => return Err(ConversionError::new(MoonValueKind::Integer, other)),
                     })
                 }
             }
@@ -120,12 +347,37 @@ macro_rules! impl_try_from_for_reduced_value {
     };
 }
 
-impl_try_from_for_reduced_value! {
+impl_try_from_for_integer! {
     u8, u16, u32, u64, u128, usize,
-    i8, i16, i32, i64, i128, isize,
-    f32, f64
+    i8, i16, i32, i64, i128, isize
+}
+
+macro_rules! impl_try_from_for_decimal {
+    ($($type:ty),+) => {
+        $(
+            impl TryFrom<MoonValue> for $type{
+                type Error = ConversionError;
+
+                fn try_from(value: MoonValue) -> Result<Self, Self::Error> {
+                    Ok(match value {
+                        MoonValue::Boolean(bool) => (if bool {1}else{0}) as $type,
+                        MoonValue::Integer(int) => int as $type,
+                        MoonValue::Decimal(decimal) if !decimal.is_finite() || (decimal as $type).is_finite() => decimal as $type,
+                        MoonValue::Array(array) if !array.is_empty() => return Self::try_from(array.into_iter().next().unwrap()),
+                        MoonValue::String(string) => match <$type>::from_str(&string) {
+                            Ok(value) => value,
+                            Err(_) => return Err(ConversionError::new(MoonValueKind::Decimal, MoonValue::String(string))),
+                        },
+                        other => return Err(ConversionError::new(MoonValueKind::Decimal, other)),
+                    })
+                }
+            }
+        )+
+    };
 }
 
+impl_try_from_for_decimal! { f32, f64 }
+
 
 
 
@@ -191,6 +443,19 @@ impl<T: Into<MoonValue>, const LEN: usize> From<[T; LEN]> for MoonValue {
     }
 }
 
+impl<K: Into<MoonValue>, V: Into<MoonValue>> From<BTreeMap<K, V>> for MoonValue {
+    fn from(value: BTreeMap<K, V>) -> Self {
+        MoonValue::Map(value.into_iter().map(|(key, value)| (key.into(), value.into())).collect())
+    }
+}
+
+#[cfg(feature = "std")]
+impl<K: Into<MoonValue>, V: Into<MoonValue>> From<std::collections::HashMap<K, V>> for MoonValue {
+    fn from(value: std::collections::HashMap<K, V>) -> Self {
+        MoonValue::Map(value.into_iter().map(|(key, value)| (key.into(), value.into())).collect())
+    }
+}
+
 impl From<&str> for MoonValue {
     fn from(value: &str) -> Self {
         MoonValue::String(value.to_string())
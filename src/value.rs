@@ -7,8 +7,10 @@ use crate::engine::context::ContextBuilder;
 use crate::execution::ASTFunction;
 use crate::parsing::MoonValueKind;
 
-/// Values used as input and outputs on scripts
+/// Values used as input and outputs on scripts, new kinds of values might be added, so matching
+/// on them must have a wildcard arm.
 #[derive(Clone, PartialEq, Debug)]
+#[non_exhaustive]
 pub enum MoonValue {
     Null,
     Boolean(bool),
@@ -16,6 +18,8 @@ pub enum MoonValue {
     Decimal(f64),
     String(String),
     Array(Vec<MoonValue>),
+    /// Pairs of keys and values, keeping the order in which they were inserted.
+    Map(Vec<(MoonValue, MoonValue)>),
 }
 
 impl TryFrom<FullValue> for MoonValue {
@@ -34,6 +38,13 @@ impl TryFrom<FullValue> for MoonValue {
                 };
                 MoonValue::Array(values)
             }
+            FullValue::Map(v) => {
+                let mut entries = Vec::with_capacity(v.len());
+                for (key, value) in v {
+                    entries.push((MoonValue::try_from(key)?, MoonValue::try_from(value)?))
+                };
+                MoonValue::Map(entries)
+            }
             _ => { return Err(()); }
         })
     }
@@ -62,6 +73,21 @@ impl Display for MoonValue {
                 result.push(']');
                 f.write_str(&*result)
             }
+            MoonValue::Map(entries) => {
+                let mut result = String::new();
+                result.push('{');
+                let mut is_first_entry = true;
+                entries.iter().for_each(|(key, value)| {
+                    if is_first_entry {
+                        result.push_str(&format!("{key}: {value}"));
+                        is_first_entry = false;
+                    } else {
+                        result.push_str(&format!(", {key}: {value}"));
+                    }
+                });
+                result.push('}');
+                f.write_str(&result)
+            }
         }
     }
 }
@@ -75,6 +101,7 @@ pub(crate) enum FullValue {
     Decimal(f64),
     String(String),
     Array(Vec<FullValue>),
+    Map(Vec<(FullValue, FullValue)>),
     Function(ASTFunction),
     Variable { block_level: usize, var_index: usize },
     DirectVariable(usize),
@@ -89,6 +116,7 @@ impl PartialEq for FullValue {
             (Self::Decimal(decimal_1), Self::Decimal(decimal_2)) => decimal_1.eq(decimal_2),
             (Self::String(string_1), Self::String(string_2)) => string_1.eq(string_2),
             (Self::Array(values_1), Self::Array(values_2)) => values_1.eq(values_2),
+            (Self::Map(entries_1), Self::Map(entries_2)) => entries_1.eq(entries_2),
             (Self::Variable { block_level: block_level_1, var_index: var_index_1 },
                 Self::Variable { block_level: block_level_2, var_index: var_index_2 })
             => block_level_1.eq(block_level_2) && var_index_1.eq(var_index_2),
@@ -122,6 +150,7 @@ impl FullValue {
             Self::Decimal(_) => MoonValueKind::Decimal.get_moon_value_type().unwrap(),
             Self::String(_) => MoonValueKind::String.get_moon_value_type().unwrap(),
             Self::Array(_) => MoonValueKind::Array.get_moon_value_type().unwrap(),
+            Self::Map(_) => MoonValueKind::Map.get_moon_value_type().unwrap(),
             Self::Function(_) => MoonValueKind::Function.get_moon_value_type().unwrap(),
             Self::Variable { block_level, var_index } => {
                 return (context_builder
@@ -139,6 +168,7 @@ impl FullValue {
             FullValue::Null | FullValue::Boolean(_) | FullValue::Decimal(_) |
             FullValue::Integer(_) | FullValue::String(_) => true,
             FullValue::Array(values) => values.iter().all(|value| value.is_simple_value()),
+            FullValue::Map(entries) => entries.iter().all(|(key, value)| key.is_simple_value() && value.is_simple_value()),
             _ => false
         }
     }
@@ -153,6 +183,9 @@ impl FullValue {
             FullValue::Array(value) => MoonValue::Array(value.into_iter()
                 .map(|value| value.resolve_value_no_context())
                 .collect()),
+            FullValue::Map(entries) => MoonValue::Map(entries.into_iter()
+                .map(|(key, value)| (key.resolve_value_no_context(), value.resolve_value_no_context()))
+                .collect()),
             _ => panic!()
         }
     }